@@ -0,0 +1,313 @@
+// Copyright 2021 Victor I. Afolabi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{rule::Rule, universe::Universe, utils};
+
+/// How the universe settled, as reported by
+/// [`Universe::stabilize`](crate::Universe::stabilize).
+///
+/// Generations are counted from the state `stabilize` was called on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stabilization {
+  /// The universe stopped changing at the given generation.
+  Still(u32),
+  /// The universe repeats every `period` generations from `generation`.
+  Oscillator {
+    /// Number of generations in a cycle.
+    period: u32,
+    /// First generation of the cycle.
+    generation: u32,
+  },
+  /// The live cells reappear translated every `period` generations from
+  /// `generation`.
+  Spaceship {
+    /// Number of generations in a cycle.
+    period: u32,
+    /// `(rows, cols)` the pattern moves per cycle.
+    displacement: (i32, i32),
+    /// First generation of the cycle.
+    generation: u32,
+  },
+  /// None of the above was detected within the allowed generations.
+  Unresolved,
+}
+
+/// Symmetries a universe can have, named after their symmetry groups.
+///
+/// Rotations and mirrors are about the center of the universe.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+  /// Unchanged by a half turn.
+  C2,
+  /// Unchanged by a quarter turn. Only square universes can have it.
+  C4,
+  /// Unchanged by mirroring left to right.
+  D2,
+  /// Unchanged by mirroring left to right and top to bottom.
+  D4,
+  /// Unchanged by every rotation and mirror of the square, including
+  /// across its diagonals. Only square universes can have it.
+  D8,
+}
+
+impl Symmetry {
+  /// Every symmetry, from the smallest group to the largest.
+  pub const ALL: [Symmetry; 5] = [
+    Symmetry::C2,
+    Symmetry::D2,
+    Symmetry::C4,
+    Symmetry::D4,
+    Symmetry::D8,
+  ];
+
+  /// Whether a `width` x `height` universe can have this symmetry.
+  pub fn fits(self, width: u32, height: u32) -> bool {
+    match self {
+      Symmetry::C4 | Symmetry::D8 => width == height,
+      _ => true,
+    }
+  }
+
+  /// Get the images of `(row, col)` under every element of the group,
+  /// including the identity.
+  ///
+  /// The universe must [fit](Symmetry::fits) the symmetry.
+  pub(crate) fn images(self, row: u32, col: u32, width: u32, height: u32) -> Vec<(u32, u32)> {
+    let (flip_row, flip_col) = (height - 1 - row, width - 1 - col);
+    match self {
+      Symmetry::C2 => vec![(row, col), (flip_row, flip_col)],
+      Symmetry::C4 => vec![
+        (row, col),
+        (col, flip_row),
+        (flip_row, flip_col),
+        (flip_col, row),
+      ],
+      Symmetry::D2 => vec![(row, col), (row, flip_col)],
+      Symmetry::D4 => vec![
+        (row, col),
+        (row, flip_col),
+        (flip_row, col),
+        (flip_row, flip_col),
+      ],
+      Symmetry::D8 => vec![
+        (row, col),
+        (col, flip_row),
+        (flip_row, flip_col),
+        (flip_col, row),
+        (row, flip_col),
+        (flip_row, col),
+        (col, row),
+        (flip_col, flip_row),
+      ],
+    }
+  }
+}
+
+/// An object left over once a universe settles, as reported by
+/// [`Universe::ash`](crate::Universe::ash).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PatternKind {
+  /// The 4-cell still life.
+  Block,
+  /// The 6-cell still life shaped like a hexagon.
+  Beehive,
+  /// The 7-cell still life.
+  Loaf,
+  /// The 5-cell still life.
+  Boat,
+  /// The 6-cell still life, a boat with one more cell.
+  Ship,
+  /// The 4-cell still life shaped like a diamond.
+  Tub,
+  /// The 8-cell still life shaped like a ring.
+  Pond,
+  /// The 3-cell period 2 oscillator.
+  Blinker,
+  /// The 6-cell period 2 oscillator.
+  Toad,
+  /// The period 2 oscillator made of two blocks touching at a corner.
+  Beacon,
+  /// A still life without a name in this list.
+  StillLife {
+    /// Number of live cells.
+    population: u32,
+  },
+  /// An oscillator without a name in this list.
+  Oscillator {
+    /// Number of generations in a cycle.
+    period: u32,
+    /// Number of live cells of the phase found.
+    population: u32,
+  },
+}
+
+/// Named still lifes, in one of their orientations.
+const STILL_LIFES: &[(PatternKind, &[(i32, i32)])] = &[
+  (PatternKind::Block, &[(0, 0), (0, 1), (1, 0), (1, 1)]),
+  (
+    PatternKind::Beehive,
+    &[(0, 1), (0, 2), (1, 0), (1, 3), (2, 1), (2, 2)],
+  ),
+  (
+    PatternKind::Loaf,
+    &[(0, 1), (0, 2), (1, 0), (1, 3), (2, 1), (2, 3), (3, 2)],
+  ),
+  (PatternKind::Boat, &[(0, 0), (0, 1), (1, 0), (1, 2), (2, 1)]),
+  (
+    PatternKind::Ship,
+    &[(0, 0), (0, 1), (1, 0), (1, 2), (2, 1), (2, 2)],
+  ),
+  (PatternKind::Tub, &[(0, 1), (1, 0), (1, 2), (2, 1)]),
+  (
+    PatternKind::Pond,
+    &[
+      (0, 1),
+      (0, 2),
+      (1, 0),
+      (1, 3),
+      (2, 0),
+      (2, 3),
+      (3, 1),
+      (3, 2),
+    ],
+  ),
+];
+
+/// Named oscillators, in one of their phases and orientations.
+const OSCILLATORS: &[(PatternKind, &[(i32, i32)])] = &[
+  (PatternKind::Blinker, &[(0, 0), (0, 1), (0, 2)]),
+  (
+    PatternKind::Toad,
+    &[(0, 1), (0, 2), (0, 3), (1, 0), (1, 1), (1, 2)],
+  ),
+  (
+    PatternKind::Beacon,
+    &[(0, 0), (0, 1), (1, 0), (2, 3), (3, 2), (3, 3)],
+  ),
+];
+
+/// Classify an object from its phases, the live cells it has at each
+/// generation of a cycle of `phases.len()` generations.
+pub(crate) fn classify(phases: &[Vec<(u32, u32)>]) -> PatternKind {
+  let period = (1..=phases.len())
+    .find(|&p| {
+      phases.len().is_multiple_of(p) && phases.get(p).is_none_or(|phase| *phase == phases[0])
+    })
+    .unwrap_or(1) as u32;
+  let population = phases[0].len() as u32;
+
+  let (known, unnamed) = if period == 1 {
+    (STILL_LIFES, PatternKind::StillLife { population })
+  } else {
+    (OSCILLATORS, PatternKind::Oscillator { period, population })
+  };
+  let forms: Vec<Vec<(i32, i32)>> = phases[..period as usize]
+    .iter()
+    .flat_map(|phase| orientations(phase))
+    .collect();
+  known
+    .iter()
+    .find(|(_, cells)| forms.iter().any(|form| form.as_slice() == *cells))
+    .map_or(unnamed, |&(kind, _)| kind)
+}
+
+/// Get the eight rotations and mirrors of `cells`, each moved to the
+/// origin and sorted.
+fn orientations(cells: &[(u32, u32)]) -> Vec<Vec<(i32, i32)>> {
+  (0..8)
+    .map(|transform| {
+      let mut form: Vec<(i32, i32)> = cells
+        .iter()
+        .map(|&(row, col)| {
+          let (mut row, mut col) = (row as i32, col as i32);
+          if transform & 1 != 0 {
+            row = -row;
+          }
+          if transform & 2 != 0 {
+            col = -col;
+          }
+          if transform & 4 != 0 {
+            std::mem::swap(&mut row, &mut col);
+          }
+          (row, col)
+        })
+        .collect();
+      let top = form.iter().map(|&(row, _)| row).min().unwrap_or(0);
+      let left = form.iter().map(|&(_, col)| col).min().unwrap_or(0);
+      for cell in form.iter_mut() {
+        *cell = (cell.0 - top, cell.1 - left);
+      }
+      form.sort_unstable();
+      form
+    })
+    .collect()
+}
+
+/// Measure how quickly two rules diverge from the same random soup.
+///
+/// Two `width` x `height` soups are seeded from `seed` like
+/// [`Universe::soup`] with half of the cells alive, then each is ticked
+/// `steps` times, one under rule `a` and the other under rule `b`. The
+/// [distance](Universe::distance) between them is returned after each
+/// step.
+pub fn rule_divergence(
+  seed: u64,
+  width: u32,
+  height: u32,
+  a: &Rule,
+  b: &Rule,
+  steps: u32,
+) -> Vec<u32> {
+  let mut under_a = Universe::soup(width, height, seed, 0.5);
+  let mut under_b = under_a.clone();
+  (0..steps)
+    .map(|_| {
+      under_a.tick_with(a);
+      under_b.tick_with(b);
+      under_a
+        .distance(&under_b)
+        .expect("both universes have the same size")
+    })
+    .collect()
+}
+
+/// Digest the run of a random soup, to check that the rules and the
+/// pseudo-random seeding give the same results on every platform.
+///
+/// A `width` x `height` soup is seeded from `seed` like
+/// [`Universe::soup`] with half of the cells alive and ticked `steps`
+/// times. Its size and the cells of every generation, starting with the
+/// seeded one, are hashed together with 64-bit FNV-1a, which doesn't
+/// depend on the platform or compiler version.
+pub fn reference_digest(seed: u64, width: u32, height: u32, steps: u32) -> u64 {
+  let mut universe = Universe::soup(width, height, seed, 0.5);
+  let mut digest = utils::FNV_OFFSET_BASIS;
+  digest = utils::fnv1a(digest, &width.to_le_bytes());
+  digest = utils::fnv1a(digest, &height.to_le_bytes());
+  for step in 0..=steps {
+    if step > 0 {
+      universe.tick();
+    }
+    let cells: Vec<u8> = universe
+      .get_cells()
+      .iter()
+      .map(|&cell| cell as u8)
+      .collect();
+    digest = utils::fnv1a(digest, &cells);
+  }
+  digest
+}
@@ -14,14 +14,23 @@
 
 use wasm_bindgen::prelude::*;
 
-
 /// Each cell in the universe is represented as a single-byte.
 #[wasm_bindgen]
 #[repr(u8)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Cell {
   /// Dead cell.
   Dead = 0,
   /// Alive cell.
   Alive = 1,
 }
+
+impl Cell {
+  /// Get the opposite state of the cell.
+  pub fn toggle(self) -> Cell {
+    match self {
+      Cell::Dead => Cell::Alive,
+      Cell::Alive => Cell::Dead,
+    }
+  }
+}
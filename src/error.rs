@@ -0,0 +1,296 @@
+// Copyright 2021 Victor I. Afolabi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{error, fmt};
+
+use wasm_bindgen::prelude::*;
+
+/// Errors raised while building a universe from pattern data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+  /// A coordinate of the pattern falls outside of the universe.
+  OutOfBounds {
+    /// Row the coordinate maps to.
+    row: i64,
+    /// Column the coordinate maps to.
+    col: i64,
+  },
+  /// An unexpected character was found in the input.
+  Unexpected {
+    /// Byte offset of the character.
+    offset: usize,
+    /// The offending character.
+    found: char,
+  },
+  /// The input ended before the pattern was complete.
+  UnexpectedEnd,
+  /// A varint starting at the byte offset is longer than 64 bits.
+  InvalidVarint {
+    /// Byte offset of the varint.
+    offset: usize,
+  },
+  /// Extra data follows the end of the pattern.
+  TrailingData {
+    /// Byte offset of the extra data.
+    offset: usize,
+  },
+  /// The universe has more than
+  /// [`Universe::MAX_CELLS`](crate::Universe::MAX_CELLS) cells.
+  TooLarge {
+    /// Width of the universe.
+    width: u32,
+    /// Height of the universe.
+    height: u32,
+  },
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ParseError::OutOfBounds { row, col } => {
+        write!(f, "cell ({}, {}) is outside of the universe", row, col)
+      }
+      ParseError::Unexpected { offset, found } => {
+        write!(f, "unexpected {:?} at offset {}", found, offset)
+      }
+      ParseError::UnexpectedEnd => write!(f, "unexpected end of input"),
+      ParseError::InvalidVarint { offset } => write!(f, "invalid varint at offset {}", offset),
+      ParseError::TrailingData { offset } => write!(f, "trailing data at offset {}", offset),
+      ParseError::TooLarge { width, height } => {
+        write!(f, "a {}x{} universe is too large", width, height)
+      }
+    }
+  }
+}
+
+impl error::Error for ParseError {}
+
+/// Errors raised while validating an RLE file.
+///
+/// Lines and columns are counted from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RleError {
+  /// The file has no header line.
+  MissingHeader,
+  /// The header line isn't of the form `x = <width>, y = <height>`,
+  /// optionally followed by `, rule = <rule>`.
+  InvalidHeader {
+    /// Line of the header.
+    line: usize,
+  },
+  /// An unexpected character was found in the pattern.
+  Unexpected {
+    /// Line of the character.
+    line: usize,
+    /// Column of the character.
+    col: usize,
+    /// The offending character.
+    found: char,
+  },
+  /// A cell falls outside of the size declared by the header.
+  OutOfBounds {
+    /// Line of the cell's run.
+    line: usize,
+    /// Column of the cell's run.
+    col: usize,
+  },
+  /// The file ended before the `!` ending the pattern.
+  UnexpectedEnd,
+}
+
+impl fmt::Display for RleError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      RleError::MissingHeader => write!(f, "missing RLE header"),
+      RleError::InvalidHeader { line } => write!(f, "invalid RLE header at line {}", line),
+      RleError::Unexpected { line, col, found } => {
+        write!(f, "unexpected {:?} at line {}, column {}", found, line, col)
+      }
+      RleError::OutOfBounds { line, col } => write!(
+        f,
+        "cell at line {}, column {} is outside of the pattern",
+        line, col
+      ),
+      RleError::UnexpectedEnd => write!(f, "unexpected end of input"),
+    }
+  }
+}
+
+impl error::Error for RleError {}
+
+/// A coordinate falls outside of the universe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoordError {
+  /// Requested row.
+  pub row: u32,
+  /// Requested column.
+  pub col: u32,
+  /// Width of the universe.
+  pub width: u32,
+  /// Height of the universe.
+  pub height: u32,
+}
+
+impl fmt::Display for CoordError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "cell ({}, {}) is outside of the {}x{} universe",
+      self.row, self.col, self.width, self.height
+    )
+  }
+}
+
+impl error::Error for CoordError {}
+
+impl From<CoordError> for JsValue {
+  /// Surface the error as a JavaScript `Error` exception.
+  fn from(err: CoordError) -> JsValue {
+    JsError::new(&err.to_string()).into()
+  }
+}
+
+/// A caller-provided buffer is too small for the requested output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferTooSmall {
+  /// Number of bytes needed.
+  pub required: usize,
+  /// Number of bytes provided.
+  pub found: usize,
+}
+
+impl fmt::Display for BufferTooSmall {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "buffer of {} bytes is too small, {} bytes are required",
+      self.found, self.required
+    )
+  }
+}
+
+impl error::Error for BufferTooSmall {}
+
+impl From<BufferTooSmall> for JsValue {
+  /// Surface the error as a JavaScript `Error` exception.
+  fn from(err: BufferTooSmall) -> JsValue {
+    JsError::new(&err.to_string()).into()
+  }
+}
+
+/// Two universes were expected to have the same dimensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DimensionMismatch {
+  /// `(width, height)` of the universe the operation was called on.
+  pub expected: (u32, u32),
+  /// `(width, height)` of the other universe.
+  pub found: (u32, u32),
+}
+
+impl fmt::Display for DimensionMismatch {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "expected a {}x{} universe, found {}x{}",
+      self.expected.0, self.expected.1, self.found.0, self.found.1
+    )
+  }
+}
+
+impl error::Error for DimensionMismatch {}
+
+/// Errors raised while building text rendering options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderOptionsError {
+  /// Alive and dead cells would be drawn with the same glyph.
+  SameGlyphs(char),
+  /// A glyph is a control character, such as a newline.
+  ControlGlyph(char),
+}
+
+impl fmt::Display for RenderOptionsError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      RenderOptionsError::SameGlyphs(glyph) => {
+        write!(f, "alive and dead cells both use the glyph {:?}", glyph)
+      }
+      RenderOptionsError::ControlGlyph(glyph) => {
+        write!(f, "glyph {:?} is a control character", glyph)
+      }
+    }
+  }
+}
+
+impl error::Error for RenderOptionsError {}
+
+/// Errors raised while building a rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleError {
+  /// The rule is not written in `B/S` notation.
+  InvalidFormat(String),
+  /// A neighbor count is not in `0..=8`.
+  InvalidCount(u8),
+}
+
+impl fmt::Display for RuleError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      RuleError::InvalidFormat(rule) => write!(f, "{:?} is not a B/S rule", rule),
+      RuleError::InvalidCount(count) => write!(f, "neighbor count {} is not in 0..=8", count),
+    }
+  }
+}
+
+impl error::Error for RuleError {}
+
+impl From<RuleError> for JsValue {
+  /// Surface the error as a JavaScript `Error` exception.
+  fn from(err: RuleError) -> JsValue {
+    JsError::new(&err.to_string()).into()
+  }
+}
+
+/// Errors raised while decoding an image.
+#[cfg(feature = "image")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageError {
+  /// The image is not a PNG, PGM or PPM image, or is an interlaced PNG.
+  UnsupportedFormat,
+  /// The dimensions, pixel format or maximum value of the image are
+  /// invalid.
+  InvalidHeader,
+  /// A sample is larger than the maximum value of the image, or a palette
+  /// index is past the end of its palette.
+  InvalidSample(u32),
+  /// The compressed pixel data of the image is corrupt.
+  InvalidData,
+  /// The image ended before every pixel was read.
+  UnexpectedEnd,
+}
+
+#[cfg(feature = "image")]
+impl fmt::Display for ImageError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ImageError::UnsupportedFormat => write!(f, "unsupported image format"),
+      ImageError::InvalidHeader => write!(f, "invalid image header"),
+      ImageError::InvalidSample(value) => write!(f, "sample {} exceeds the maximum value", value),
+      ImageError::InvalidData => write!(f, "corrupt image data"),
+      ImageError::UnexpectedEnd => write!(f, "unexpected end of image"),
+    }
+  }
+}
+
+#[cfg(feature = "image")]
+impl error::Error for ImageError {}
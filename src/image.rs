@@ -0,0 +1,34 @@
+// Copyright 2021 Victor I. Afolabi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decoding of images to luminance, for seeding universes from pictures.
+
+use crate::{error::ImageError, netpbm, png};
+
+/// A decoded image, as one luminance value in `0..=255` per pixel.
+pub(crate) struct Luma {
+  pub(crate) width: u32,
+  pub(crate) height: u32,
+  pub(crate) pixels: Vec<u8>,
+}
+
+/// Decode a PNG, PGM or PPM image to luminance, telling the format apart
+/// by its leading bytes.
+pub(crate) fn decode_luma(bytes: &[u8]) -> Result<Luma, ImageError> {
+  if bytes.starts_with(&png::SIGNATURE) {
+    png::decode_luma(bytes)
+  } else {
+    netpbm::decode_luma(bytes)
+  }
+}
@@ -0,0 +1,319 @@
+// Copyright 2021 Victor I. Afolabi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decompression of zlib streams (RFC 1950) holding DEFLATE data
+//! (RFC 1951), as found in PNG images.
+
+use crate::error::ImageError;
+
+/// Base length of the length symbols 257 to 285.
+const LENGTH_BASE: [u16; 29] = [
+  3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+  163, 195, 227, 258,
+];
+/// Extra bits read after the length symbols 257 to 285.
+const LENGTH_EXTRA: [u8; 29] = [
+  0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+/// Base distance of the distance symbols 0 to 29.
+const DISTANCE_BASE: [u16; 30] = [
+  1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049,
+  3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+/// Extra bits read after the distance symbols 0 to 29.
+const DISTANCE_EXTRA: [u8; 30] = [
+  0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13,
+];
+/// Order the code lengths of the code length alphabet are stored in.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+  16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// Decompress a zlib stream, failing if it holds more than `limit` bytes.
+///
+/// The Adler-32 checksum at the end of the stream isn't checked.
+pub(crate) fn zlib(bytes: &[u8], limit: usize) -> Result<Vec<u8>, ImageError> {
+  let (method, flags) = match bytes {
+    [method, flags, ..] => (*method, *flags),
+    _ => return Err(ImageError::UnexpectedEnd),
+  };
+  // Only DEFLATE without a preset dictionary is used by PNG.
+  let header = (u16::from(method) << 8) | u16::from(flags);
+  if method & 0x0f != 8 || header % 31 != 0 || flags & 0x20 != 0 {
+    return Err(ImageError::InvalidData);
+  }
+
+  let mut inflater = Inflater {
+    bits: Bits {
+      bytes,
+      offset: 2,
+      buffer: 0,
+      count: 0,
+    },
+    out: Vec::new(),
+    limit,
+  };
+  loop {
+    let last = inflater.bits.read(1)? == 1;
+    match inflater.bits.read(2)? {
+      0 => inflater.stored()?,
+      1 => inflater.codes(&Huffman::fixed_literals(), &Huffman::fixed_distances())?,
+      2 => {
+        let (literals, distances) = inflater.dynamic_codes()?;
+        inflater.codes(&literals, &distances)?
+      }
+      _ => return Err(ImageError::InvalidData),
+    }
+    if last {
+      return Ok(inflater.out);
+    }
+  }
+}
+
+/// Reader of the bits of a stream, least significant bit first.
+struct Bits<'a> {
+  bytes: &'a [u8],
+  offset: usize,
+  buffer: u32,
+  count: u32,
+}
+
+impl<'a> Bits<'a> {
+  /// Read `count` bits, at most 16, as a number.
+  fn read(&mut self, count: u32) -> Result<u32, ImageError> {
+    while self.count < count {
+      let byte = *self
+        .bytes
+        .get(self.offset)
+        .ok_or(ImageError::UnexpectedEnd)?;
+      self.offset += 1;
+      self.buffer |= u32::from(byte) << self.count;
+      self.count += 8;
+    }
+    let value = self.buffer & ((1 << count) - 1);
+    self.buffer >>= count;
+    self.count -= count;
+    Ok(value)
+  }
+
+  /// Skip to the next byte boundary and take the bytes from there on.
+  fn align(&mut self) -> &'a [u8] {
+    // Fewer than 8 bits are ever buffered, so they all belong to the
+    // current byte.
+    self.buffer = 0;
+    self.count = 0;
+    &self.bytes[self.offset..]
+  }
+}
+
+/// A canonical Huffman code, as the number of codes of each length and
+/// the symbols ordered by code.
+struct Huffman {
+  counts: [u16; 16],
+  symbols: Vec<u16>,
+}
+
+impl Huffman {
+  /// Build the code giving each symbol the code length at its index, 0
+  /// for unused symbols.
+  fn new(lengths: &[u8]) -> Result<Huffman, ImageError> {
+    let mut counts = [0u16; 16];
+    for &length in lengths {
+      counts[usize::from(length)] += 1;
+    }
+    counts[0] = 0;
+
+    // Reject codes with more codes of some length than can exist.
+    let mut left = 1i32;
+    for &count in &counts[1..] {
+      left = 2 * left - i32::from(count);
+      if left < 0 {
+        return Err(ImageError::InvalidData);
+      }
+    }
+
+    let mut offsets = [0u16; 16];
+    for length in 1..15 {
+      offsets[length + 1] = offsets[length] + counts[length];
+    }
+    let mut symbols = vec![0; lengths.len()];
+    for (symbol, &length) in lengths.iter().enumerate() {
+      if length != 0 {
+        let offset = &mut offsets[usize::from(length)];
+        symbols[usize::from(*offset)] = symbol as u16;
+        *offset += 1;
+      }
+    }
+    Ok(Huffman { counts, symbols })
+  }
+
+  /// The fixed code of literals and lengths.
+  fn fixed_literals() -> Huffman {
+    let mut lengths = [8; 288];
+    lengths[144..256].iter_mut().for_each(|length| *length = 9);
+    lengths[256..280].iter_mut().for_each(|length| *length = 7);
+    Huffman::new(&lengths).expect("the fixed code is complete")
+  }
+
+  /// The fixed code of distances.
+  fn fixed_distances() -> Huffman {
+    Huffman::new(&[5; 30]).expect("the fixed code is complete")
+  }
+
+  /// Read the next symbol.
+  fn decode(&self, bits: &mut Bits) -> Result<u16, ImageError> {
+    // Codes of each length are consecutive numbers, following on from the
+    // shorter ones, so the code is read a bit at a time until it falls in
+    // the range of its length.
+    let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+    for &count in &self.counts[1..] {
+      code |= bits.read(1)? as i32;
+      let count = i32::from(count);
+      if code - first < count {
+        return Ok(self.symbols[(index + code - first) as usize]);
+      }
+      index += count;
+      first = (first + count) << 1;
+      code <<= 1;
+    }
+    Err(ImageError::InvalidData)
+  }
+}
+
+/// State of the decompression of a DEFLATE stream.
+struct Inflater<'a> {
+  bits: Bits<'a>,
+  out: Vec<u8>,
+  limit: usize,
+}
+
+impl<'a> Inflater<'a> {
+  /// Check that `len` more bytes fit in the output limit.
+  fn check_room(&self, len: usize) -> Result<(), ImageError> {
+    if self.out.len() + len > self.limit {
+      return Err(ImageError::InvalidData);
+    }
+    Ok(())
+  }
+
+  /// Copy an uncompressed block.
+  fn stored(&mut self) -> Result<(), ImageError> {
+    let bytes = self.bits.align();
+    let (len, rest) = match bytes {
+      [len_lo, len_hi, nlen_lo, nlen_hi, rest @ ..] => {
+        let len = u16::from_le_bytes([*len_lo, *len_hi]);
+        if len != !u16::from_le_bytes([*nlen_lo, *nlen_hi]) {
+          return Err(ImageError::InvalidData);
+        }
+        (usize::from(len), rest)
+      }
+      _ => return Err(ImageError::UnexpectedEnd),
+    };
+    let block = rest.get(..len).ok_or(ImageError::UnexpectedEnd)?;
+    self.check_room(len)?;
+    self.out.extend_from_slice(block);
+    self.bits.offset += 4 + len;
+    Ok(())
+  }
+
+  /// Read the codes of a block compressed with its own Huffman codes.
+  fn dynamic_codes(&mut self) -> Result<(Huffman, Huffman), ImageError> {
+    let literals = self.bits.read(5)? as usize + 257;
+    let distances = self.bits.read(5)? as usize + 1;
+    let code_lengths = self.bits.read(4)? as usize + 4;
+    if literals > 286 || distances > 30 {
+      return Err(ImageError::InvalidData);
+    }
+
+    let mut lengths = [0u8; 19];
+    for &symbol in &CODE_LENGTH_ORDER[..code_lengths] {
+      lengths[symbol] = self.bits.read(3)? as u8;
+    }
+    let code_length_code = Huffman::new(&lengths)?;
+
+    // The literal and distance code lengths are one sequence, in which
+    // runs may cross from one code to the other.
+    let mut lengths = vec![0u8; literals + distances];
+    let mut index = 0;
+    while index < lengths.len() {
+      let (length, repeat) = match code_length_code.decode(&mut self.bits)? {
+        symbol @ 0..=15 => (symbol as u8, 1),
+        16 => {
+          let previous = *index
+            .checked_sub(1)
+            .and_then(|previous| lengths.get(previous))
+            .ok_or(ImageError::InvalidData)?;
+          (previous, 3 + self.bits.read(2)? as usize)
+        }
+        17 => (0, 3 + self.bits.read(3)? as usize),
+        _ => (0, 11 + self.bits.read(7)? as usize),
+      };
+      let run = lengths
+        .get_mut(index..index + repeat)
+        .ok_or(ImageError::InvalidData)?;
+      run.iter_mut().for_each(|slot| *slot = length);
+      index += repeat;
+    }
+    // A block can't be ended without a code for its end.
+    if lengths[256] == 0 {
+      return Err(ImageError::InvalidData);
+    }
+
+    Ok((
+      Huffman::new(&lengths[..literals])?,
+      Huffman::new(&lengths[literals..])?,
+    ))
+  }
+
+  /// Decode a block compressed with the given Huffman codes.
+  fn codes(&mut self, literals: &Huffman, distances: &Huffman) -> Result<(), ImageError> {
+    loop {
+      let symbol = usize::from(literals.decode(&mut self.bits)?);
+      match symbol {
+        0..=255 => {
+          self.check_room(1)?;
+          self.out.push(symbol as u8);
+        }
+        256 => return Ok(()),
+        _ => {
+          let symbol = symbol - 257;
+          if symbol >= LENGTH_BASE.len() {
+            return Err(ImageError::InvalidData);
+          }
+          let len = usize::from(LENGTH_BASE[symbol])
+            + self.bits.read(u32::from(LENGTH_EXTRA[symbol]))? as usize;
+
+          let symbol = usize::from(distances.decode(&mut self.bits)?);
+          if symbol >= DISTANCE_BASE.len() {
+            return Err(ImageError::InvalidData);
+          }
+          let distance = usize::from(DISTANCE_BASE[symbol])
+            + self.bits.read(u32::from(DISTANCE_EXTRA[symbol]))? as usize;
+          if distance > self.out.len() {
+            return Err(ImageError::InvalidData);
+          }
+
+          // The copy may overlap the bytes it produces, so it goes a byte
+          // at a time.
+          self.check_room(len)?;
+          let start = self.out.len() - distance;
+          for i in 0..len {
+            let byte = self.out[start + i];
+            self.out.push(byte);
+          }
+        }
+      }
+    }
+  }
+}
@@ -5,24 +5,38 @@
 //! comes in one of three flavors:
 //!
 //! 1. Keep track of which subset of the universe has interesting things happening,
-//! and expand this region as needed. In the wrost case, this expansion is unbounded
-//! and the implementation will get slower eventually run out of memory.
+//!    and expand this region as needed. In the wrost case, this expansion is unbounded
+//!    and the implementation will get slower eventually run out of memory.
 //!
 //! 2. Create a fixed-size universe, where cells on the edges have fewer neighbors
-//! than cells in the middle. The downside with this approach is that infinite patterns,
-//! like gliders, that reach the end of the universe are snuffed out.
+//!    than cells in the middle. The downside with this approach is that infinite patterns,
+//!    like gliders, that reach the end of the universe are snuffed out.
 //!
 //! 3. Create a fixed-size, periodic universe, where cells on the edges have neighbors
-//! that wrap around to the other side of the universe. Because neighbors wrap around
-//! the edges of the universe, gliders can keep running forever.
+//!    that wrap around to the other side of the universe. Because neighbors wrap around
+//!    the edges of the universe, gliders can keep running forever.
 //!
 //! The third option is implemented here.
 
 #[macro_use]
 mod macros;
 
+mod analysis;
 mod cells;
+mod error;
+#[cfg(feature = "image")]
+mod image;
+#[cfg(feature = "image")]
+mod inflate;
+mod neighborhood;
+#[cfg(feature = "image")]
+mod netpbm;
 mod options;
+mod patterns;
+#[cfg(feature = "image")]
+mod png;
+mod rle;
+mod rule;
 mod universe;
 mod utils;
 
@@ -32,6 +46,17 @@ mod utils;
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
+pub use analysis::{reference_digest, rule_divergence, PatternKind, Stabilization, Symmetry};
 pub use cells::Cell;
-pub use options::RenderOptions;
-pub use universe::Universe;
+#[cfg(feature = "image")]
+pub use error::ImageError;
+pub use error::{
+  BufferTooSmall, CoordError, DimensionMismatch, ParseError, RenderOptionsError, RleError,
+  RuleError,
+};
+pub use neighborhood::Neighborhood;
+pub use options::{RenderOptions, TextRenderOptions, TextRenderOptionsBuilder};
+pub use patterns::{register_pattern, BUILT_IN_PATTERNS};
+pub use rle::validate_rle;
+pub use rule::Rule;
+pub use universe::{Checkpoint, Universe};
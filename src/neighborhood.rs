@@ -0,0 +1,47 @@
+// Copyright 2021 Victor I. Afolabi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The neighbors a cell counts when deciding its next state.
+
+use wasm_bindgen::prelude::*;
+
+/// The layout of the neighbors of a cell.
+///
+/// Either way, neighbors wrap around the edges of the universe.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Neighborhood {
+  /// The 8 cells around a cell on the square grid. This is the default,
+  /// and the classic Game of Life.
+  Moore,
+  /// The 6 cells around a cell on a hexagonal grid, laid over the
+  /// rectangular storage in "odd-r" offset coordinates: odd rows are
+  /// shifted half a cell to the right of even rows.
+  ///
+  /// A cell on an even row neighbors columns `col - 1` and `col` of the
+  /// rows above and below, and a cell on an odd row columns `col` and
+  /// `col + 1`, besides its left and right neighbors. Since the offset
+  /// alternates with the row, only universes with an even height wrap
+  /// seamlessly from the bottom row to the top one.
+  Hex,
+}
+
+/// Offsets, as `(rows, cols)`, of the neighbors of a cell on an even row
+/// of the hexagonal grid.
+pub(crate) const HEX_EVEN_ROW: [(i8, i8); 6] =
+  [(-1, -1), (-1, 0), (0, -1), (0, 1), (1, -1), (1, 0)];
+
+/// Offsets, as `(rows, cols)`, of the neighbors of a cell on an odd row
+/// of the hexagonal grid.
+pub(crate) const HEX_ODD_ROW: [(i8, i8); 6] = [(-1, 0), (-1, 1), (0, -1), (0, 1), (1, 0), (1, 1)];
@@ -0,0 +1,129 @@
+// Copyright 2021 Victor I. Afolabi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decoding of grayscale and color Netpbm images (PGM and PPM).
+
+use crate::{error::ImageError, image::Luma, utils};
+
+/// Decode a PGM (`P2`, `P5`) or PPM (`P3`, `P6`) image to luminance.
+///
+/// Color pixels are converted with the Rec. 601 luma weights, and samples
+/// are scaled from the image's maximum value to `0..=255`. Images of more
+/// than [`Universe::MAX_CELLS`](crate::Universe::MAX_CELLS) pixels are
+/// rejected as an invalid header.
+pub(crate) fn decode_luma(bytes: &[u8]) -> Result<Luma, ImageError> {
+  let mut reader = Reader { bytes, offset: 0 };
+  let (channels, binary) = match reader.token()? {
+    b"P2" => (1, false),
+    b"P3" => (3, false),
+    b"P5" => (1, true),
+    b"P6" => (3, true),
+    _ => return Err(ImageError::UnsupportedFormat),
+  };
+  let width = reader.number()?;
+  let height = reader.number()?;
+  let max = reader.number()?;
+  if width == 0 || height == 0 || max == 0 || max > 0xffff {
+    return Err(ImageError::InvalidHeader);
+  }
+  if binary {
+    // A single whitespace byte separates the header from the raster.
+    reader.offset += 1;
+  }
+
+  let count = utils::cell_count(width, height).ok_or(ImageError::InvalidHeader)?;
+  // Every sample takes at least one byte, so a short image is rejected
+  // before reserving room for the pixels its header claims.
+  let bytes_per_sample = if binary && max > 0xff { 2 } else { 1 };
+  if reader.bytes.len().saturating_sub(reader.offset) < count * channels * bytes_per_sample {
+    return Err(ImageError::UnexpectedEnd);
+  }
+
+  let mut pixels = Vec::with_capacity(count);
+  for _ in 0..count {
+    let mut channel = [0u32; 3];
+    for sample in channel.iter_mut().take(channels) {
+      let value = match (binary, max > 0xff) {
+        (false, _) => reader.number()?,
+        (true, false) => u32::from(reader.byte()?),
+        (true, true) => u32::from(reader.byte()?) << 8 | u32::from(reader.byte()?),
+      };
+      if value > max {
+        return Err(ImageError::InvalidSample(value));
+      }
+      *sample = value * 255 / max;
+    }
+    let luma = if channels == 1 {
+      channel[0]
+    } else {
+      (299 * channel[0] + 587 * channel[1] + 114 * channel[2]) / 1000
+    };
+    pixels.push(luma as u8);
+  }
+
+  Ok(Luma {
+    width,
+    height,
+    pixels,
+  })
+}
+
+/// Cursor over the bytes of an image.
+struct Reader<'a> {
+  bytes: &'a [u8],
+  offset: usize,
+}
+
+impl<'a> Reader<'a> {
+  /// Read a single raw byte.
+  fn byte(&mut self) -> Result<u8, ImageError> {
+    let byte = *self
+      .bytes
+      .get(self.offset)
+      .ok_or(ImageError::UnexpectedEnd)?;
+    self.offset += 1;
+    Ok(byte)
+  }
+
+  /// Read the next whitespace-separated token, skipping `#` comments.
+  fn token(&mut self) -> Result<&'a [u8], ImageError> {
+    loop {
+      match self.bytes.get(self.offset) {
+        Some(b'#') => {
+          while !matches!(self.bytes.get(self.offset), None | Some(b'\n')) {
+            self.offset += 1;
+          }
+        }
+        Some(byte) if byte.is_ascii_whitespace() => self.offset += 1,
+        Some(_) => break,
+        None => return Err(ImageError::UnexpectedEnd),
+      }
+    }
+
+    let start = self.offset;
+    while matches!(self.bytes.get(self.offset), Some(byte) if !byte.is_ascii_whitespace()) {
+      self.offset += 1;
+    }
+    Ok(&self.bytes[start..self.offset])
+  }
+
+  /// Read the next token as a decimal number.
+  fn number(&mut self) -> Result<u32, ImageError> {
+    let token = self.token()?;
+    std::str::from_utf8(token)
+      .ok()
+      .and_then(|token| token.parse().ok())
+      .ok_or(ImageError::InvalidHeader)
+  }
+}
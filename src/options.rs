@@ -14,6 +14,8 @@
 
 use wasm_bindgen::prelude::*;
 
+use crate::error::RenderOptionsError;
+
 /// Rendering options for the Game of life.
 #[wasm_bindgen]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -28,3 +30,99 @@ pub enum RenderOptions {
   /// Use when on a low memory device.
   Text,
 }
+
+/// Options controlling how the universe is rendered as text.
+///
+/// Build one with [`TextRenderOptions::builder`] and render with
+/// [`Universe::render_with`](crate::Universe::render_with).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextRenderOptions {
+  /// Glyph used for alive cells.
+  pub(crate) alive_glyph: char,
+  /// Glyph used for dead cells.
+  pub(crate) dead_glyph: char,
+  /// 256-color ANSI foreground of alive cells, if any.
+  pub(crate) alive_fg: Option<u8>,
+  /// 256-color ANSI background of dead cells, if any.
+  pub(crate) dead_bg: Option<u8>,
+  /// Whether the last line is terminated by a newline.
+  pub(crate) trailing_newline: bool,
+}
+
+impl TextRenderOptions {
+  /// Start building text rendering options from the defaults.
+  pub fn builder() -> TextRenderOptionsBuilder {
+    TextRenderOptionsBuilder {
+      options: TextRenderOptions::default(),
+    }
+  }
+}
+
+impl Default for TextRenderOptions {
+  /// The same output as [`Universe::render`](crate::Universe::render).
+  fn default() -> TextRenderOptions {
+    TextRenderOptions {
+      alive_glyph: '◼',
+      dead_glyph: '◻',
+      alive_fg: None,
+      dead_bg: None,
+      trailing_newline: true,
+    }
+  }
+}
+
+/// Builder for [`TextRenderOptions`], validated on [`build`](Self::build).
+#[derive(Debug, Clone, Copy)]
+pub struct TextRenderOptionsBuilder {
+  options: TextRenderOptions,
+}
+
+impl TextRenderOptionsBuilder {
+  /// Set the glyph used for alive cells.
+  pub fn alive_glyph(mut self, glyph: char) -> Self {
+    self.options.alive_glyph = glyph;
+    self
+  }
+
+  /// Set the glyph used for dead cells.
+  pub fn dead_glyph(mut self, glyph: char) -> Self {
+    self.options.dead_glyph = glyph;
+    self
+  }
+
+  /// Color alive cells with a 256-color ANSI foreground.
+  pub fn alive_fg(mut self, color: u8) -> Self {
+    self.options.alive_fg = Some(color);
+    self
+  }
+
+  /// Color dead cells with a 256-color ANSI background.
+  pub fn dead_bg(mut self, color: u8) -> Self {
+    self.options.dead_bg = Some(color);
+    self
+  }
+
+  /// Set whether the last line is terminated by a newline.
+  pub fn trailing_newline(mut self, trailing_newline: bool) -> Self {
+    self.options.trailing_newline = trailing_newline;
+    self
+  }
+
+  /// Validate and build the options.
+  ///
+  /// The alive and dead glyphs must differ, and neither can be a control
+  /// character since it would break the grid layout.
+  pub fn build(self) -> Result<TextRenderOptions, RenderOptionsError> {
+    let options = self.options;
+    for &glyph in [options.alive_glyph, options.dead_glyph].iter() {
+      if glyph.is_control() {
+        return Err(RenderOptionsError::ControlGlyph(glyph));
+      }
+    }
+    if options.alive_glyph == options.dead_glyph {
+      return Err(RenderOptionsError::SameGlyphs(options.alive_glyph));
+    }
+
+    Ok(options)
+  }
+}
@@ -0,0 +1,76 @@
+// Copyright 2021 Victor I. Afolabi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A registry of named patterns, to stamp into a universe by name.
+//!
+//! The registry starts with a few well known patterns, see [`BUILT_IN_PATTERNS`].
+//! Patterns registered with [`register_pattern`] take precedence over
+//! built-ins of the same name.
+
+use std::sync::Mutex;
+
+/// Patterns available without registering them, as the `(row, col)` of
+/// their alive cells relative to their top-left corner.
+pub const BUILT_IN_PATTERNS: &[(&str, &[(u32, u32)])] = &[
+  ("glider", &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]),
+  ("blinker", &[(0, 0), (0, 1), (0, 2)]),
+  ("block", &[(0, 0), (0, 1), (1, 0), (1, 1)]),
+  ("toad", &[(0, 1), (0, 2), (0, 3), (1, 0), (1, 1), (1, 2)]),
+  ("beacon", &[(0, 0), (0, 1), (1, 0), (2, 3), (3, 2), (3, 3)]),
+  (
+    "lwss",
+    &[
+      (0, 1),
+      (0, 4),
+      (1, 0),
+      (2, 0),
+      (2, 4),
+      (3, 0),
+      (3, 1),
+      (3, 2),
+      (3, 3),
+    ],
+  ),
+];
+
+/// A pattern registered at runtime, with its name.
+type Registered = (String, Vec<(u32, u32)>);
+
+/// Patterns registered at runtime, most recent last.
+static REGISTRY: Mutex<Vec<Registered>> = Mutex::new(Vec::new());
+
+/// Register a pattern under `name`, replacing any pattern with that name.
+///
+/// `coords` are the `(row, col)` of the alive cells relative to the
+/// top-left corner of the pattern.
+pub fn register_pattern(name: &str, coords: Vec<(u32, u32)>) {
+  let mut registry = REGISTRY.lock().unwrap_or_else(|err| err.into_inner());
+  registry.retain(|(registered, _)| registered != name);
+  registry.push((name.to_string(), coords));
+}
+
+/// Get the cells of the pattern registered or built in under `name`.
+pub(crate) fn lookup(name: &str) -> Option<Vec<(u32, u32)>> {
+  let registry = REGISTRY.lock().unwrap_or_else(|err| err.into_inner());
+  registry
+    .iter()
+    .find(|(registered, _)| registered == name)
+    .map(|(_, coords)| coords.clone())
+    .or_else(|| {
+      BUILT_IN_PATTERNS
+        .iter()
+        .find(|(built_in, _)| *built_in == name)
+        .map(|(_, coords)| coords.to_vec())
+    })
+}
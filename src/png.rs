@@ -0,0 +1,210 @@
+// Copyright 2021 Victor I. Afolabi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decoding of PNG images.
+
+use crate::{error::ImageError, image::Luma, inflate, utils};
+
+/// The bytes every PNG image starts with.
+pub(crate) const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// Decode a PNG image to luminance.
+///
+/// Every bit depth and color type is supported, but not interlaced
+/// images. Color pixels are converted with the Rec. 601 luma weights,
+/// alpha is ignored, and chunk CRCs aren't checked. Images of more than
+/// [`Universe::MAX_CELLS`](crate::Universe::MAX_CELLS) pixels are rejected
+/// as an invalid header.
+pub(crate) fn decode_luma(bytes: &[u8]) -> Result<Luma, ImageError> {
+  let mut chunks = Chunks {
+    bytes,
+    offset: SIGNATURE.len(),
+  };
+  let header = match chunks.next_chunk()? {
+    (b"IHDR", data) if data.len() == 13 => data,
+    _ => return Err(ImageError::InvalidHeader),
+  };
+  let width = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+  let height = u32::from_be_bytes([header[4], header[5], header[6], header[7]]);
+  let (depth, color_type) = (header[8], header[9]);
+  let channels = match (color_type, depth) {
+    (0, 1) | (0, 2) | (0, 4) | (0, 8) | (0, 16) => 1,
+    (2, 8) | (2, 16) => 3,
+    (3, 1) | (3, 2) | (3, 4) | (3, 8) => 1,
+    (4, 8) | (4, 16) => 2,
+    (6, 8) | (6, 16) => 4,
+    _ => return Err(ImageError::InvalidHeader),
+  };
+  if header[10] != 0 || header[11] != 0 {
+    return Err(ImageError::InvalidHeader);
+  }
+  if header[12] != 0 {
+    return Err(ImageError::UnsupportedFormat);
+  }
+  let count = utils::cell_count(width, height).ok_or(ImageError::InvalidHeader)?;
+  if count == 0 {
+    return Err(ImageError::InvalidHeader);
+  }
+
+  let mut palette = &[][..];
+  let mut data = Vec::new();
+  loop {
+    match chunks.next_chunk()? {
+      (b"PLTE", entries) => palette = entries,
+      (b"IDAT", part) => data.extend_from_slice(part),
+      (b"IEND", _) => break,
+      _ => {}
+    }
+  }
+  if color_type == 3 && palette.is_empty() {
+    return Err(ImageError::InvalidHeader);
+  }
+
+  // Every row starts with the byte naming its filter.
+  let bits_per_pixel = channels * usize::from(depth);
+  let stride = (width as usize * bits_per_pixel).div_ceil(8);
+  let raw = inflate::zlib(&data, height as usize * (1 + stride))?;
+  if raw.len() != height as usize * (1 + stride) {
+    return Err(ImageError::UnexpectedEnd);
+  }
+
+  let max = (1u32 << depth) - 1;
+  let mut pixels = Vec::with_capacity(count);
+  let mut previous = vec![0; stride];
+  let mut row = vec![0; stride];
+  for filtered in raw.chunks(1 + stride) {
+    unfilter(
+      filtered[0],
+      &filtered[1..],
+      &previous,
+      &mut row,
+      bits_per_pixel.div_ceil(8),
+    )?;
+    for col in 0..width as usize {
+      let scaled = |channel: usize| sample(&row, col * channels + channel, depth) * 255 / max;
+      let (r, g, b) = match color_type {
+        0 | 4 => (scaled(0), scaled(0), scaled(0)),
+        2 | 6 => (scaled(0), scaled(1), scaled(2)),
+        _ => {
+          let index = sample(&row, col, depth) as usize;
+          let entry = palette
+            .get(3 * index..3 * index + 3)
+            .ok_or(ImageError::InvalidSample(index as u32))?;
+          (
+            u32::from(entry[0]),
+            u32::from(entry[1]),
+            u32::from(entry[2]),
+          )
+        }
+      };
+      let luma = (299 * r + 587 * g + 114 * b) / 1000;
+      pixels.push(luma as u8);
+    }
+    std::mem::swap(&mut previous, &mut row);
+  }
+
+  Ok(Luma {
+    width,
+    height,
+    pixels,
+  })
+}
+
+/// Cursor over the chunks of an image.
+struct Chunks<'a> {
+  bytes: &'a [u8],
+  offset: usize,
+}
+
+impl<'a> Chunks<'a> {
+  /// Read the type and data of the next chunk.
+  fn next_chunk(&mut self) -> Result<(&'a [u8], &'a [u8]), ImageError> {
+    let rest = &self.bytes[self.offset..];
+    if rest.len() < 12 {
+      return Err(ImageError::UnexpectedEnd);
+    }
+    let len = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+    // The chunk data is followed by its CRC.
+    if rest.len() - 12 < len {
+      return Err(ImageError::UnexpectedEnd);
+    }
+    let data = &rest[8..8 + len];
+    self.offset += 12 + len;
+    Ok((&rest[4..8], data))
+  }
+}
+
+/// Undo the filter of a row of `filtered` bytes, given the row above it,
+/// into `row`.
+///
+/// `step` is the number of bytes per pixel, rounded up to 1, which the
+/// filters look back by.
+fn unfilter(
+  filter: u8,
+  filtered: &[u8],
+  previous: &[u8],
+  row: &mut [u8],
+  step: usize,
+) -> Result<(), ImageError> {
+  for i in 0..row.len() {
+    let left = if i >= step { row[i - step] } else { 0 };
+    let up = previous[i];
+    let up_left = if i >= step { previous[i - step] } else { 0 };
+    let predicted = match filter {
+      0 => 0,
+      1 => left,
+      2 => up,
+      3 => ((u16::from(left) + u16::from(up)) / 2) as u8,
+      4 => paeth(left, up, up_left),
+      _ => return Err(ImageError::InvalidData),
+    };
+    row[i] = filtered[i].wrapping_add(predicted);
+  }
+  Ok(())
+}
+
+/// Predict a byte from its neighbors to the left, above, and above left,
+/// picking whichever is closest to `left + up - up_left`.
+fn paeth(left: u8, up: u8, up_left: u8) -> u8 {
+  let (a, b, c) = (i16::from(left), i16::from(up), i16::from(up_left));
+  let estimate = a + b - c;
+  let (to_a, to_b, to_c) = (
+    (estimate - a).abs(),
+    (estimate - b).abs(),
+    (estimate - c).abs(),
+  );
+  if to_a <= to_b && to_a <= to_c {
+    left
+  } else if to_b <= to_c {
+    up
+  } else {
+    up_left
+  }
+}
+
+/// Read sample `index` of an unfiltered row of `depth`-bit samples.
+fn sample(row: &[u8], index: usize, depth: u8) -> u32 {
+  match depth {
+    8 => u32::from(row[index]),
+    16 => u32::from(row[2 * index]) << 8 | u32::from(row[2 * index + 1]),
+    _ => {
+      // Samples of less than a byte are packed from the most significant
+      // bit.
+      let depth = usize::from(depth);
+      let bit = index * depth;
+      let shift = 8 - depth - bit % 8;
+      u32::from(row[bit / 8] >> shift) & ((1 << depth) - 1)
+    }
+  }
+}
@@ -0,0 +1,217 @@
+// Copyright 2021 Victor I. Afolabi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Run Length Encoded (RLE) pattern bodies.
+//!
+//! A body is a sequence of `<count><tag>` items where the count is optional
+//! and defaults to 1. `b` is a dead cell, `o` an alive cell, `$` ends a
+//! row and `!` ends the pattern. Trailing dead cells of a row are omitted.
+//!
+//! A full RLE file precedes the body with `#` comment lines and a header
+//! line such as `x = 3, y = 2, rule = B3/S23`, giving its size.
+
+use crate::{
+  cells::Cell,
+  error::{ParseError, RleError},
+  rule::Rule,
+};
+
+/// Encode `cells`, laid out in rows of `width`, as an RLE body.
+pub(crate) fn encode(cells: &[Cell], width: u32) -> String {
+  let mut output = String::new();
+  let mut pending_rows = 0;
+
+  // Without any columns there are no cells, and so no rows.
+  for line in cells.chunks(width.max(1) as usize) {
+    // Trailing dead cells of a row are implied by the end of the row.
+    let len = line
+      .iter()
+      .rposition(|&cell| cell == Cell::Alive)
+      .map_or(0, |i| i + 1);
+    if len == 0 {
+      pending_rows += 1;
+      continue;
+    }
+    // A row ends the previous one, plus one `$` per skipped empty row.
+    let row_breaks = pending_rows + usize::from(!output.is_empty());
+    if row_breaks > 0 {
+      push_run(&mut output, row_breaks, '$');
+    }
+    pending_rows = 0;
+
+    let mut run = (line[0], 0);
+    for &cell in &line[..len] {
+      if cell != run.0 {
+        push_run(&mut output, run.1, tag(run.0));
+        run = (cell, 0);
+      }
+      run.1 += 1;
+    }
+    push_run(&mut output, run.1, tag(run.0));
+  }
+
+  output.push('!');
+  output
+}
+
+/// Decode an RLE body into the `(row, col)` of its alive cells, rejecting
+/// cells that fall outside of a `width` x `height` universe.
+pub(crate) fn decode(body: &str, width: u32, height: u32) -> Result<Vec<(u32, u32)>, ParseError> {
+  let mut alive = Vec::new();
+  walk(body, width, height, |row, col| alive.push((row, col))).map_err(|(_, err)| err)?;
+  Ok(alive)
+}
+
+/// Check that `rle` is a well formed RLE file, returning the `(width,
+/// height)` declared by its header.
+///
+/// Only the header and body are parsed, no universe is built. Errors
+/// report the 1-based line and column they were found at.
+///
+/// # Example
+///
+/// ```rust
+/// use game_of_life::{validate_rle, RleError};
+///
+/// let glider = "#C A glider\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!";
+/// assert_eq!(validate_rle(glider), Ok((3, 3)));
+///
+/// let error = validate_rle("x = 3, y = 3\nbob$2bo$3x!");
+/// assert_eq!(
+///   error,
+///   Err(RleError::Unexpected {
+///     line: 2,
+///     col: 10,
+///     found: 'x',
+///   })
+/// );
+/// ```
+pub fn validate_rle(rle: &str) -> Result<(u32, u32), RleError> {
+  let mut lines = rle.split_inclusive('\n');
+  let mut offset = 0;
+  let mut line = 0;
+
+  let (width, height) = loop {
+    let text = lines.next().ok_or(RleError::MissingHeader)?;
+    offset += text.len();
+    line += 1;
+    let text = text.trim();
+    if text.is_empty() || text.starts_with('#') {
+      continue;
+    }
+    break parse_header(text).ok_or(RleError::InvalidHeader { line })?;
+  };
+
+  let body = &rle[offset..];
+  walk(body, width, height, |_, _| {}).map_err(|(at, err)| {
+    // The body starts on the line after the header.
+    let before = &body[..at];
+    let line = line + 1 + before.matches('\n').count();
+    let col = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+    match err {
+      ParseError::OutOfBounds { .. } => RleError::OutOfBounds { line, col },
+      ParseError::Unexpected { found, .. } => RleError::Unexpected { line, col, found },
+      _ => RleError::UnexpectedEnd,
+    }
+  })?;
+
+  Ok((width, height))
+}
+
+/// Parse a `x = <width>, y = <height>[, rule = <rule>]` header line.
+fn parse_header(text: &str) -> Option<(u32, u32)> {
+  let (mut width, mut height) = (None, None);
+  for (i, field) in text.split(',').enumerate() {
+    let mut parts = field.splitn(2, '=');
+    let key = parts.next()?.trim();
+    let value = parts.next()?.trim();
+    match (i, key) {
+      (0, "x") => width = Some(value.parse().ok()?),
+      (1, "y") => height = Some(value.parse().ok()?),
+      (2, "rule") => {
+        value.parse::<Rule>().ok()?;
+      }
+      _ => return None,
+    }
+  }
+  Some((width?, height?))
+}
+
+/// Walk an RLE body, calling `alive` with the `(row, col)` of each alive
+/// cell. Errors come with the byte offset they were found at.
+fn walk<F>(body: &str, width: u32, height: u32, mut alive: F) -> Result<(), (usize, ParseError)>
+where
+  F: FnMut(u32, u32),
+{
+  let (mut row, mut col) = (0u32, 0u32);
+  let mut count: Option<u32> = None;
+
+  for (offset, c) in body.char_indices() {
+    match c {
+      '0'..='9' => {
+        let digit = c.to_digit(10).unwrap();
+        let value = count
+          .unwrap_or(0)
+          .checked_mul(10)
+          .and_then(|value| value.checked_add(digit))
+          .ok_or((offset, ParseError::Unexpected { offset, found: c }))?;
+        count = Some(value);
+      }
+      'b' | 'o' | '$' => {
+        let run = count.take().unwrap_or(1);
+        match c {
+          'b' => col = col.saturating_add(run),
+          'o' => {
+            for _ in 0..run {
+              if row >= height || col >= width {
+                let err = ParseError::OutOfBounds {
+                  row: i64::from(row),
+                  col: i64::from(col),
+                };
+                return Err((offset, err));
+              }
+              alive(row, col);
+              col += 1;
+            }
+          }
+          _ => {
+            row = row.saturating_add(run);
+            col = 0;
+          }
+        }
+      }
+      '!' if count.is_none() => return Ok(()),
+      c if c.is_whitespace() && count.is_none() => {}
+      _ => return Err((offset, ParseError::Unexpected { offset, found: c })),
+    }
+  }
+
+  Err((body.len(), ParseError::UnexpectedEnd))
+}
+
+/// The RLE tag of a cell state.
+fn tag(cell: Cell) -> char {
+  match cell {
+    Cell::Alive => 'o',
+    Cell::Dead => 'b',
+  }
+}
+
+/// Append a run of `count` tags, omitting the count when it is 1.
+fn push_run(output: &mut String, count: usize, tag: char) {
+  if count > 1 {
+    output.push_str(&count.to_string());
+  }
+  output.push(tag);
+}
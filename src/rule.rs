@@ -0,0 +1,157 @@
+// Copyright 2021 Victor I. Afolabi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{fmt, str::FromStr};
+
+use crate::{cells::Cell, error::RuleError};
+
+/// A life-like rule, given by the neighbor counts which cause a dead cell
+/// to be born and an alive cell to survive.
+///
+/// Rules are written in `B/S` notation, such as `B3/S23` for Conway's Game
+/// of Life.
+///
+/// Example:
+///
+/// ```rust
+/// use game_of_life::Rule;
+/// let highlife: Rule = "B36/S23".parse().unwrap();
+/// assert_eq!(highlife.birth(), &[3, 6]);
+/// assert_eq!(highlife.survival(), &[2, 3]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+  /// Sorted neighbor counts causing a birth, padded with zeros.
+  birth: [u8; 9],
+  birth_len: u8,
+  /// Sorted neighbor counts allowing survival, padded with zeros.
+  survival: [u8; 9],
+  survival_len: u8,
+}
+
+impl Rule {
+  /// Create a rule from its birth and survival neighbor counts.
+  ///
+  /// Counts may be given in any order and repeated, but must be `0..=8`.
+  pub fn new(birth: &[u8], survival: &[u8]) -> Result<Rule, RuleError> {
+    let (birth, birth_len) = Rule::counts(birth)?;
+    let (survival, survival_len) = Rule::counts(survival)?;
+    Ok(Rule {
+      birth,
+      birth_len,
+      survival,
+      survival_len,
+    })
+  }
+
+  /// Conway's Game of Life, `B3/S23`.
+  pub fn conway() -> Rule {
+    Rule::new(&[3], &[2, 3]).expect("B3/S23 is a valid rule")
+  }
+
+  /// Get the neighbor counts causing a dead cell to be born, in order.
+  pub fn birth(&self) -> &[u8] {
+    &self.birth[..self.birth_len as usize]
+  }
+
+  /// Get the neighbor counts allowing an alive cell to survive, in order.
+  pub fn survival(&self) -> &[u8] {
+    &self.survival[..self.survival_len as usize]
+  }
+
+  /// Get the next state of a cell given its number of live neighbors.
+  pub(crate) fn next(&self, cell: Cell, live_neighbors: u8) -> Cell {
+    let counts = match cell {
+      Cell::Dead => self.birth(),
+      Cell::Alive => self.survival(),
+    };
+    if counts.contains(&live_neighbors) {
+      Cell::Alive
+    } else {
+      Cell::Dead
+    }
+  }
+
+  /// Validate, sort and deduplicate neighbor counts.
+  fn counts(counts: &[u8]) -> Result<([u8; 9], u8), RuleError> {
+    let mut present = [false; 9];
+    for &count in counts {
+      *present
+        .get_mut(count as usize)
+        .ok_or(RuleError::InvalidCount(count))? = true;
+    }
+
+    let mut sorted = [0; 9];
+    let mut len = 0;
+    for count in (0..9).filter(|&count| present[count as usize]) {
+      sorted[len] = count;
+      len += 1;
+    }
+    Ok((sorted, len as u8))
+  }
+}
+
+impl Default for Rule {
+  fn default() -> Rule {
+    Rule::conway()
+  }
+}
+
+impl FromStr for Rule {
+  type Err = RuleError;
+
+  /// Parse a rule in `B3/S23` notation, in any case and with the parts in
+  /// either order.
+  fn from_str(rule: &str) -> Result<Rule, RuleError> {
+    let (mut birth, mut survival) = (None, None);
+    for part in rule.trim().split('/') {
+      let mut chars = part.chars();
+      let slot = match chars.next().map(|c| c.to_ascii_uppercase()) {
+        Some('B') => &mut birth,
+        Some('S') => &mut survival,
+        _ => return Err(RuleError::InvalidFormat(rule.to_string())),
+      };
+      if slot.is_some() {
+        return Err(RuleError::InvalidFormat(rule.to_string()));
+      }
+
+      let counts = chars
+        .map(|c| match c.to_digit(10) {
+          Some(digit) => Ok(digit as u8),
+          None => Err(RuleError::InvalidFormat(rule.to_string())),
+        })
+        .collect::<Result<Vec<u8>, RuleError>>()?;
+      *slot = Some(counts);
+    }
+
+    match (birth, survival) {
+      (Some(birth), Some(survival)) => Rule::new(&birth, &survival),
+      _ => Err(RuleError::InvalidFormat(rule.to_string())),
+    }
+  }
+}
+
+impl fmt::Display for Rule {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "B")?;
+    for count in self.birth() {
+      write!(f, "{}", count)?;
+    }
+    write!(f, "/S")?;
+    for count in self.survival() {
+      write!(f, "{}", count)?;
+    }
+    Ok(())
+  }
+}
@@ -12,14 +12,31 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt;
+use std::{
+  collections::{hash_map::DefaultHasher, HashMap},
+  convert::TryFrom,
+  fmt,
+  hash::{Hash, Hasher},
+};
 
 use wasm_bindgen::prelude::*;
 
-use crate::{cells::Cell, utils};
+use crate::{
+  analysis::{self, PatternKind, Stabilization, Symmetry},
+  cells::Cell,
+  error::{BufferTooSmall, CoordError, DimensionMismatch, ParseError, RuleError},
+  neighborhood::{self, Neighborhood},
+  options::TextRenderOptions,
+  patterns, rle,
+  rule::Rule,
+  utils,
+};
+#[cfg(feature = "image")]
+use crate::{error::ImageError, image};
 
 /// The Game of Life universe.
 #[wasm_bindgen]
+#[derive(Clone)]
 pub struct Universe {
   /// The width of the universe.
   width: u32,
@@ -27,9 +44,187 @@ pub struct Universe {
   height: u32,
   /// The cells of the universe of length `width * height`.
   cells: Vec<Cell>,
+  /// Scratch buffer the next generation is written into during a tick.
+  next: Vec<Cell>,
+  /// The number of generations the universe has been ticked.
+  generation: u64,
+  /// Total `(births, deaths)` over the lifetime of the universe, when
+  /// tracked.
+  transitions: Option<(u64, u64)>,
+  /// The rule cells evolve by.
+  rule: Rule,
+  /// Rectangles evolving by their own rule, latest first in precedence.
+  regions: Vec<RegionRule>,
+  /// Default size in pixels of a cell in the raster renderers.
+  cell_px: u32,
+  /// Pixels drawn by [`Universe::render_rgba_buffer`], kept to be reused
+  /// across frames.
+  rgba: Vec<u8>,
+  /// The neighbors each cell counts.
+  neighborhood: Neighborhood,
+}
+
+/// A rectangle of the universe evolving by its own rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RegionRule {
+  top: u32,
+  left: u32,
+  height: u32,
+  width: u32,
+  rule: Rule,
+}
+
+/// A snapshot of a universe that can later be restored.
+///
+/// See [`Universe::checkpoint`] and [`Universe::restore`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+  width: u32,
+  height: u32,
+  cells: Vec<Cell>,
+  generation: u64,
+  transitions: Option<(u64, u64)>,
+}
+
+impl Checkpoint {
+  /// Get the cells of the snapshot, laid out like [`Universe::get_cells`].
+  pub fn cells(&self) -> &[Cell] {
+    &self.cells
+  }
+
+  /// Get the width of the universe the snapshot was taken of.
+  pub fn width(&self) -> u32 {
+    self.width
+  }
+
+  /// Get the height of the universe the snapshot was taken of.
+  pub fn height(&self) -> u32 {
+    self.height
+  }
+
+  /// Get the generation the snapshot was taken at.
+  pub fn generation(&self) -> u64 {
+    self.generation
+  }
+
+  /// Count the live cells of the snapshot.
+  pub fn population(&self) -> u32 {
+    population_of(&self.cells)
+  }
+
+  /// Get the `(row, col)` coordinates of the live cells of the snapshot,
+  /// in row-major order like [`Universe::live_cells`].
+  pub fn live_cells(&self) -> Vec<(u32, u32)> {
+    live_cells_of(&self.cells, self.width)
+  }
+}
+
+/// Every state a universe went through, to detect when it repeats one.
+///
+/// States are compared by hash first, and only a matching hash is
+/// confirmed by comparing the cells.
+#[derive(Default)]
+struct StateHistory {
+  states: Vec<(u64, Vec<Cell>)>,
+}
+
+impl StateHistory {
+  /// Get the index of the earlier state identical to `cells`, or record
+  /// `cells` as a new state and return `None`.
+  fn repeat_or_record(&mut self, cells: &[Cell]) -> Option<usize> {
+    let state_hash = hash_of(cells);
+    let repeated = self
+      .states
+      .iter()
+      .position(|(seen_hash, seen)| *seen_hash == state_hash && seen.as_slice() == cells);
+    if repeated.is_none() {
+      self.states.push((state_hash, cells.to_vec()));
+    }
+    repeated
+  }
 }
 
 /// Public methods, exported to JavaScript.
+/// A line between two cells, traced with Bresenham's algorithm.
+///
+/// The line takes one step at a time along its longer axis, and any cell
+/// along it can be found without walking the steps before it.
+struct Line {
+  start: (u32, u32),
+  end: (u32, u32),
+  steps: u32,
+}
+
+impl Line {
+  fn new(start: (u32, u32), end: (u32, u32)) -> Line {
+    let steps = start.0.abs_diff(end.0).max(start.1.abs_diff(end.1));
+    Line { start, end, steps }
+  }
+
+  /// Get the cell `step` steps from the start of the line.
+  fn at(&self, step: u32) -> (u32, u32) {
+    // Bresenham's error term amounts to rounding the exact position along
+    // each axis, with halves rounded away from the start.
+    let along = |from: u32, to: u32| {
+      let span = u128::from(from.abs_diff(to));
+      let steps = u128::from(self.steps.max(1));
+      let offset = ((2 * span * u128::from(step) + steps) / (2 * steps)) as u32;
+      if to >= from {
+        from + offset
+      } else {
+        from - offset
+      }
+    };
+    (
+      along(self.start.0, self.end.0),
+      along(self.start.1, self.end.1),
+    )
+  }
+
+  /// Get the first and last steps landing inside of a `height` x `width`
+  /// universe, or `None` if the line misses it.
+  fn steps_within(&self, height: u32, width: u32) -> Option<(u32, u32)> {
+    let (rows_first, rows_last) = self.steps_below(|(row, _)| row, height)?;
+    let (cols_first, cols_last) = self.steps_below(|(_, col)| col, width)?;
+    let (first, last) = (rows_first.max(cols_first), rows_last.min(cols_last));
+    if first <= last {
+      Some((first, last))
+    } else {
+      None
+    }
+  }
+
+  /// Get the first and last steps where `axis` of the cell is below
+  /// `limit`.
+  ///
+  /// Both axes only ever move one way along the line, so these steps are
+  /// contiguous.
+  fn steps_below(&self, axis: impl Fn((u32, u32)) -> u32, limit: u32) -> Option<(u32, u32)> {
+    let below = |step: u32| axis(self.at(step)) < limit;
+    match (below(0), below(self.steps)) {
+      (true, true) => Some((0, self.steps)),
+      (false, false) => None,
+      (true, false) => Some((0, self.first_step(|step| !below(step)) - 1)),
+      (false, true) => Some((self.first_step(below), self.steps)),
+    }
+  }
+
+  /// Get the first step at which `reached` holds, given that it holds at
+  /// the end of the line and keeps holding once it does.
+  fn first_step(&self, reached: impl Fn(u32) -> bool) -> u32 {
+    let (mut low, mut high) = (0, self.steps);
+    while low < high {
+      let mid = low + (high - low) / 2;
+      if reached(mid) {
+        high = mid;
+      } else {
+        low = mid + 1;
+      }
+    }
+    low
+  }
+}
+
 #[wasm_bindgen]
 impl Universe {
   /// Create a new universe with the given width and height.
@@ -38,20 +233,110 @@ impl Universe {
 
     let cells = (0..width * height)
       .map(|i| {
-        // if js_sys::Math::random() < 0.5 {
         if i % 2 == 0 || i % 7 == 0 {
           Cell::Alive
         } else {
           Cell::Dead
         }
       })
-      .collect();
+      .collect::<Vec<Cell>>();
+    let next = cells.clone();
 
     Universe {
       width,
       height,
       cells,
+      next,
+      generation: 0,
+      transitions: None,
+      rule: Rule::conway(),
+      regions: Vec::new(),
+      cell_px: 1,
+      rgba: Vec::new(),
+      neighborhood: Neighborhood::Moore,
+    }
+  }
+
+  /// Create a dead universe like [`Universe::dead`] from dimensions read
+  /// from untrusted data, rejecting those of more than
+  /// [`Universe::MAX_CELLS`] cells.
+  fn checked_dead(width: u32, height: u32) -> Result<Universe, ParseError> {
+    utils::cell_count(width, height).ok_or(ParseError::TooLarge { width, height })?;
+    Ok(Universe::dead(width, height))
+  }
+
+  /// Create a universe where each cell is alive with probability
+  /// `density`, drawn from a pseudo-random generator seeded with `seed`.
+  ///
+  /// The same seed always produces the same universe, on every platform.
+  pub fn soup(width: u32, height: u32, seed: u64, density: f64) -> Universe {
+    let mut universe = Universe::dead(width, height);
+    let mut rng = utils::Rng::new(seed);
+    for cell in universe.cells.iter_mut() {
+      if rng.next_f64() < density {
+        *cell = Cell::Alive;
+      }
+    }
+    universe
+  }
+
+  /// Create a soup like [`Universe::soup`] which has the given symmetry.
+  ///
+  /// Only one cell of each set of cells mapped onto each other by the
+  /// symmetry is drawn at random, and the others copy it.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the universe can't have the symmetry, see
+  /// [`Symmetry::fits`].
+  pub fn symmetric_soup(
+    width: u32,
+    height: u32,
+    seed: u64,
+    density: f64,
+    symmetry: Symmetry,
+  ) -> Universe {
+    assert!(
+      symmetry.fits(width, height),
+      "a {}x{} universe can't have {:?} symmetry",
+      width,
+      height,
+      symmetry
+    );
+
+    let mut universe = Universe::dead(width, height);
+    let mut rng = utils::Rng::new(seed);
+    for row in 0..height {
+      for col in 0..width {
+        // Cells are visited in scan order, so the first cell of each set
+        // is drawn before the others copy it.
+        let (first_row, first_col) = symmetry
+          .images(row, col, width, height)
+          .into_iter()
+          .min()
+          .expect("the identity is an image");
+        let idx = universe.get_index(row, col);
+        universe.cells[idx] = if (first_row, first_col) == (row, col) {
+          if rng.next_f64() < density {
+            Cell::Alive
+          } else {
+            Cell::Dead
+          }
+        } else {
+          universe.cells[universe.get_index(first_row, first_col)]
+        };
+      }
     }
+    universe
+  }
+
+  /// Create a universe where half of the cells are alive at random.
+  ///
+  /// The generator is seeded from the current time, so every universe
+  /// differs. Use [`Universe::soup`] for reproducible universes.
+  pub fn new_random(width: u32, height: u32) -> Universe {
+    utils::set_panic_hook();
+    Universe::soup(width, height, utils::time_seed(), 0.5)
   }
 
   /// Encode the rules of the universe to determine
@@ -69,43 +354,44 @@ impl Universe {
   ///               as if by reproduction.
   ///  - **Otherwise**: All other cells remain in the same state.
   ///
+  /// These are Conway's rules, `B3/S23`, which universes start with. Other
+  /// life-like rules can be chosen with [`Universe::set_rule`].
   pub fn tick(&mut self) {
-    let mut next = self.cells.clone();
+    self.tick_in_place_no_alloc();
+  }
+
+  /// Advance the universe by `n` generations.
+  pub fn tick_many(&mut self, n: u32) {
+    for _ in 0..n {
+      self.tick();
+    }
+  }
 
+  /// Advance the universe by one generation without allocating.
+  ///
+  /// The next generation is written into a scratch buffer that lives
+  /// alongside the cells and the two buffers are swapped afterwards, so
+  /// this is safe to call from contexts that forbid allocation. The
+  /// buffers are only (re)allocated when the universe is resized.
+  pub fn tick_in_place_no_alloc(&mut self) {
     for row in 0..self.height {
       for col in 0..self.width {
         let idx = self.get_index(row, col);
         let cell = self.cells[idx];
-        let live_neighbors = self.live_neighbor_count(row, col);
-
-        log!(
-          "cell[{}, {}] is initially {:?} and has {} live neighbors",
-          row,
-          col,
-          cell,
-          live_neighbors
-        );
+        let next_cell = self.next_cell(row, col);
+        self.next[idx] = next_cell;
 
-        let next_cell = match (cell, live_neighbors) {
-          // Rule 1: Any live cell with fewer than two live neighbours
-          // dies, as if caused by underpopulation.
-          (Cell::Alive, x) if x < 2 => Cell::Dead,
-          // Rule 2: Any live cell with two or three live neighbours
-          // lives on to the next generation.
-          (Cell::Alive, 2) | (Cell::Alive, 3) => Cell::Alive,
-          // Rule 3: Any live cell with more than three live
-          // neighbours dies, as if by overpopulation.
-          (Cell::Alive, x) if x > 3 => Cell::Dead,
-          // Rule 4: Any dead cell with exactly three live neighbours
-          // becomes a live cell, as if by reproduction.
-          (Cell::Dead, 3) => Cell::Alive,
-          // All other cells remain in the same state.
-          (otherwise, _) => otherwise,
-        };
-        next[idx] = next_cell;
+        if let Some((births, deaths)) = self.transitions.as_mut() {
+          match (cell, next_cell) {
+            (Cell::Dead, Cell::Alive) => *births += 1,
+            (Cell::Alive, Cell::Dead) => *deaths += 1,
+            _ => {}
+          }
+        }
       }
     }
-    self.cells = next;
+    std::mem::swap(&mut self.cells, &mut self.next);
+    self.generation += 1;
   }
 
   /// Render the universe as a string.
@@ -116,6 +402,21 @@ impl Universe {
     self.to_string()
   }
 
+  /// Render the universe as a string of 256-color ANSI escape sequences.
+  ///
+  /// Alive cells are drawn as `◼` in the `alive_fg` foreground color and
+  /// dead cells as a space on the `dead_bg` background color. Every line
+  /// ends with a reset (`ESC[0m`) so the colors never bleed past the grid.
+  pub fn render_ansi_color(&self, alive_fg: u8, dead_bg: u8) -> String {
+    let options = TextRenderOptions::builder()
+      .dead_glyph(' ')
+      .alive_fg(alive_fg)
+      .dead_bg(dead_bg)
+      .build()
+      .expect("the ANSI glyphs are valid");
+    self.render_with(&options)
+  }
+
   /// Get the width of the universe.
   pub fn width(&self) -> u32 {
     self.width
@@ -127,6 +428,7 @@ impl Universe {
   pub fn set_width(&mut self, width: u32) {
     self.width = width;
     self.cells = (0..width * self.height).map(|_i| Cell::Dead).collect();
+    self.next = self.cells.clone();
   }
 
   /// Get the height of the universe.
@@ -140,20 +442,522 @@ impl Universe {
   pub fn set_height(&mut self, height: u32) {
     self.height = height;
     self.cells = (0..self.width * height).map(|_i| Cell::Dead).collect();
+    self.next = self.cells.clone();
   }
 
   /// Get the entire cells in the universe.
   pub fn cells(&self) -> *const Cell {
     self.cells.as_ptr()
   }
+
+  /// Get the flat `row * width + col` index of every live cell, in scan
+  /// order, such as to fill an instanced rendering buffer.
+  pub fn live_cell_indices(&self) -> Vec<u32> {
+    self
+      .cells
+      .iter()
+      .enumerate()
+      .filter(|(_, &cell)| cell == Cell::Alive)
+      .map(|(idx, _)| idx as u32)
+      .collect()
+  }
+
+  /// Get the number of generations the universe has been ticked.
+  pub fn generation(&self) -> u64 {
+    self.generation
+  }
+
+  /// Set the generation counter, such as when resuming a saved run.
+  ///
+  /// The cells are left untouched.
+  pub fn set_generation(&mut self, generation: u64) {
+    self.generation = generation;
+  }
+
+  /// Restart the generation counter at 0, leaving the cells untouched.
+  pub fn reset_generation(&mut self) {
+    self.set_generation(0);
+  }
+
+  /// Get the number of alive cells in the universe.
+  pub fn population(&self) -> u32 {
+    population_of(&self.cells)
+  }
+
+  /// Toggle the state of a cell.
+  ///
+  /// Coordinates outside of the universe wrap around its edges.
+  pub fn toggle_cell(&mut self, row: u32, col: u32) {
+    let idx = self.get_index(row % self.height, col % self.width);
+    self.cells[idx] = self.cells[idx].toggle();
+  }
+
+  /// Set the state of a cell.
+  ///
+  /// Coordinates outside of the universe are rejected, which is thrown as
+  /// an `Error` in JavaScript.
+  pub fn set_cell(&mut self, row: u32, col: u32, state: Cell) -> Result<(), CoordError> {
+    let idx = self.checked_index(row, col)?;
+    self.cells[idx] = state;
+    Ok(())
+  }
+
+  /// Set the cells along the line from `(r0, c0)` to `(r1, c1)`, both
+  /// ends included, to `state`.
+  ///
+  /// The line is traced with Bresenham's algorithm, and the cells which
+  /// fall outside of the universe are skipped.
+  pub fn draw_line(&mut self, r0: u32, c0: u32, r1: u32, c1: u32, state: Cell) {
+    // The ends may be far outside of the universe, so only the steps
+    // landing inside of it are visited.
+    let line = Line::new((r0, c0), (r1, c1));
+    if let Some((first, last)) = line.steps_within(self.height, self.width) {
+      for step in first..=last {
+        let (row, col) = line.at(step);
+        let idx = self.get_index(row, col);
+        self.cells[idx] = state;
+      }
+    }
+  }
+
+  /// Set the cells on the border of the `height` x `width` rectangle
+  /// starting at `(top, left)` to `state`, leaving its inside untouched.
+  ///
+  /// The cells which fall outside of the universe are skipped.
+  pub fn draw_rect_outline(&mut self, top: u32, left: u32, height: u32, width: u32, state: Cell) {
+    if height == 0 || width == 0 || top >= self.height || left >= self.width {
+      return;
+    }
+    let bottom = top.saturating_add(height - 1);
+    let right = left.saturating_add(width - 1);
+    self.draw_line(top, left, top, right, state);
+    self.draw_line(bottom, left, bottom, right, state);
+    self.draw_line(top, left, bottom, left, state);
+    self.draw_line(top, right, bottom, right, state);
+  }
+
+  /// Set the cells on the circle of `radius` around `(cr, cc)` to
+  /// `state`, traced with the midpoint circle algorithm.
+  ///
+  /// The cells which fall outside of the universe are skipped, and a
+  /// radius of 0 is the center alone.
+  pub fn draw_circle(&mut self, cr: u32, cc: u32, radius: u32, state: Cell) {
+    if self.height == 0
+      || self.width == 0
+      || cr.saturating_sub(radius) >= self.height
+      || cc.saturating_sub(radius) >= self.width
+    {
+      return;
+    }
+
+    // Step `y` of the octant puts cells `y` rows or `y` columns away from
+    // the center, so only the steps within that distance of the universe
+    // can land on it.
+    let reach = |center: u32, len: u32| {
+      center.saturating_sub(len - 1)..=center.max((len - 1).saturating_sub(center))
+    };
+    let (center_row, center_col) = (i64::from(cr), i64::from(cc));
+    for y in reach(cr, self.height).chain(reach(cc, self.width)) {
+      let x = midpoint_circle_x(radius, y);
+      if x < y {
+        continue;
+      }
+      let (x, y) = (i64::from(x), i64::from(y));
+      for &(row, col) in [
+        (y, x),
+        (x, y),
+        (x, -y),
+        (y, -x),
+        (-y, -x),
+        (-x, -y),
+        (-x, y),
+        (-y, x),
+      ]
+      .iter()
+      {
+        self.set_cell_clipped(center_row + row, center_col + col, state);
+      }
+    }
+  }
+
+  /// Mirror the top-left quadrant into the other three, giving the
+  /// universe 4-fold mirror symmetry.
+  ///
+  /// The top-left quadrant spans `(height + 1) / 2` rows and
+  /// `(width + 1) / 2` columns, so with odd dimensions the center row and
+  /// column belong to both halves and are mirrored onto themselves.
+  pub fn mirror_quadrants(&mut self) {
+    for row in 0..self.height {
+      for col in 0..self.width {
+        let source_row = row.min(self.height - 1 - row);
+        let source_col = col.min(self.width - 1 - col);
+        let source = self.cells[self.get_index(source_row, source_col)];
+        let idx = self.get_index(row, col);
+        self.cells[idx] = source;
+      }
+    }
+  }
+
+  /// Get the rule of the universe in `B/S` notation, such as `B3/S23`.
+  #[wasm_bindgen(js_name = rule)]
+  pub fn rule_string(&self) -> String {
+    self.rule.to_string()
+  }
+
+  /// Get the neighbors each cell counts.
+  pub fn neighborhood(&self) -> Neighborhood {
+    self.neighborhood
+  }
+
+  /// Set the neighbors each cell counts, such as [`Neighborhood::Hex`] for
+  /// hexagonal life-like rules.
+  pub fn set_neighborhood(&mut self, neighborhood: Neighborhood) {
+    self.neighborhood = neighborhood;
+  }
+
+  /// Set the rule of the universe from `B/S` notation, such as `B36/S23`.
+  ///
+  /// Invalid rules are rejected, which is thrown as an `Error` in
+  /// JavaScript.
+  pub fn set_rule(&mut self, rule: &str) -> Result<(), RuleError> {
+    self.rule = rule.parse()?;
+    Ok(())
+  }
+
+  /// Set the rule of the universe from its birth and survival counts,
+  /// such as `[3]` and `[2, 3]` for Conway's rules.
+  ///
+  /// Counts above 8 are rejected, which is thrown as an `Error` in
+  /// JavaScript.
+  pub fn set_rule_from_sets(&mut self, birth: &[u8], survival: &[u8]) -> Result<(), RuleError> {
+    self.rule = Rule::new(birth, survival)?;
+    Ok(())
+  }
+
+  /// Get the size in pixels of a cell in the raster renderers.
+  pub fn cell_px(&self) -> u32 {
+    self.cell_px
+  }
+
+  /// Set the size in pixels of a cell used by the raster renderers when
+  /// none is given explicitly.
+  ///
+  /// Sizes are clamped to `1..=`[`Universe::MAX_CELL_PX`], here and in the
+  /// renderers taking an explicit size.
+  pub fn set_cell_px(&mut self, cell_px: u32) {
+    self.cell_px = clamp_cell_px(cell_px);
+  }
+
+  /// Render the universe as RGBA pixels in row-major order, with each cell
+  /// drawn as a [`Universe::cell_px`] sized square.
+  ///
+  /// Colors are packed as `0xRRGGBBAA`. The buffer can be wrapped in an
+  /// `ImageData` and drawn straight onto a `<canvas>`.
+  pub fn render_rgba(&self, alive: u32, dead: u32) -> Vec<u8> {
+    self.render_rgba_scaled(alive, dead, self.cell_px)
+  }
+
+  /// Render the universe like [`Universe::render_rgba`], with each cell
+  /// drawn as a `cell_px` sized square instead of the stored size.
+  pub fn render_rgba_scaled(&self, alive: u32, dead: u32, cell_px: u32) -> Vec<u8> {
+    let mut buf = vec![0; self.frame_len(cell_px)];
+    self
+      .render_rgba_scaled_into(&mut buf, alive, dead, cell_px)
+      .expect("the buffer is sized for the universe");
+    buf
+  }
+
+  /// Render the universe like [`Universe::render_rgba`], into a buffer
+  /// kept by the universe and reused across frames.
+  ///
+  /// JavaScript can view the pixels in the wasm memory through
+  /// [`Universe::rgba_ptr`] and [`Universe::rgba_len`] without copying
+  /// them, like the cells through [`Universe::cells`]. The buffer is only
+  /// reallocated when the size of the frame changes, which also moves it.
+  pub fn render_rgba_buffer(&mut self, alive: u32, dead: u32) {
+    let mut rgba = std::mem::take(&mut self.rgba);
+    rgba.resize(self.frame_len(self.cell_px), 0);
+    self
+      .render_rgba_into(&mut rgba, alive, dead)
+      .expect("the buffer is sized for the universe");
+    self.rgba = rgba;
+  }
+
+  /// Get a pointer to the pixels drawn by the last
+  /// [`Universe::render_rgba_buffer`].
+  pub fn rgba_ptr(&self) -> *const u8 {
+    self.rgba.as_ptr()
+  }
+
+  /// Get the number of bytes drawn by the last
+  /// [`Universe::render_rgba_buffer`].
+  pub fn rgba_len(&self) -> usize {
+    self.rgba.len()
+  }
+
+  /// Count the live cells with at least one dead cell above, below, left
+  /// or right of them, wrapping around the edges of the universe.
+  ///
+  /// Together with the population this measures how compact a pattern is.
+  pub fn perimeter(&self) -> u32 {
+    let mut count = 0;
+    for row in 0..self.height {
+      for col in 0..self.width {
+        if self.cells[self.get_index(row, col)] == Cell::Dead {
+          continue;
+        }
+        let neighbors = [
+          ((row + self.height - 1) % self.height, col),
+          ((row + 1) % self.height, col),
+          (row, (col + self.width - 1) % self.width),
+          (row, (col + 1) % self.width),
+        ];
+        if neighbors
+          .iter()
+          .any(|&(r, c)| self.cells[self.get_index(r, c)] == Cell::Dead)
+        {
+          count += 1;
+        }
+      }
+    }
+    count
+  }
+
+  /// Advance one generation and render it with [`Universe::render_rgba`],
+  /// in a single call across the JavaScript boundary.
+  pub fn tick_and_return_rgba(&mut self, alive: u32, dead: u32) -> Vec<u8> {
+    self.tick();
+    self.render_rgba(alive, dead)
+  }
+
+  /// Advance `n` generations and render the last one with
+  /// [`Universe::render_rgba`].
+  ///
+  /// This is meant for web workers: the simulation runs off the UI thread
+  /// and the returned buffer can be transferred to it without a copy.
+  pub fn tick_many_to_buffer(&mut self, n: u32, alive: u32, dead: u32) -> Vec<u8> {
+    self.tick_many(n);
+    self.render_rgba(alive, dead)
+  }
+
+  /// Get the number of live neighbors of every cell, in the same order as
+  /// [`Universe::cells`].
+  pub fn neighbor_counts(&self) -> Vec<u8> {
+    let mut buf = vec![0; self.cells.len()];
+    self
+      .neighbor_counts_into(&mut buf)
+      .expect("the buffer is sized for the universe");
+    buf
+  }
+
+  /// Fill a caller-owned buffer like [`Universe::neighbor_counts`], which
+  /// can be reused across generations.
+  ///
+  /// The buffer needs 1 byte per cell, extra bytes are left untouched. A
+  /// buffer which is too small is rejected, which is thrown as an `Error`
+  /// in JavaScript.
+  pub fn neighbor_counts_into(&self, buf: &mut [u8]) -> Result<(), BufferTooSmall> {
+    let required = self.cells.len();
+    if buf.len() < required {
+      return Err(BufferTooSmall {
+        required,
+        found: buf.len(),
+      });
+    }
+    for row in 0..self.height {
+      for col in 0..self.width {
+        buf[self.get_index(row, col)] = self.live_neighbor_count(row, col);
+      }
+    }
+    Ok(())
+  }
+
+  /// Export the universe as an ASCII PBM (`P1`) image, with each cell
+  /// drawn as a [`Universe::cell_px`] sized square and live cells black.
+  ///
+  /// Pixels are separated by spaces, and rows wider than 35 pixels are
+  /// wrapped across lines to keep them under the 70 characters readers
+  /// expect.
+  pub fn to_pbm(&self) -> String {
+    let cell_px = self.cell_px as usize;
+    let mut pbm = format!(
+      "P1\n{} {}\n",
+      self.width as usize * cell_px,
+      self.height as usize * cell_px
+    );
+    for row in self.rows() {
+      let bits: Vec<&str> = row
+        .iter()
+        .flat_map(|&cell| std::iter::repeat_n(if cell == Cell::Alive { "1" } else { "0" }, cell_px))
+        .collect();
+      for _ in 0..cell_px {
+        for line in bits.chunks(35) {
+          pbm.push_str(&line.join(" "));
+          pbm.push('\n');
+        }
+      }
+    }
+    pbm
+  }
 }
 
 impl Universe {
+  /// Largest number of cells of a universe decoded from untrusted data,
+  /// such as [`Universe::from_sparse_bytes`], so a few header bytes can't
+  /// request gigabytes of memory.
+  pub const MAX_CELLS: usize = 1 << 24;
+
+  /// Largest size in pixels of a cell in the raster renderers.
+  pub const MAX_CELL_PX: u32 = 64;
+
+  /// Render the universe as text using the given options.
+  ///
+  /// Whenever the cell state changes along a line, the colors are reset
+  /// before the new state's color is applied, and colored lines end with
+  /// a reset so they never bleed past the grid.
+  pub fn render_with(&self, options: &TextRenderOptions) -> String {
+    let colored = options.alive_fg.is_some() || options.dead_bg.is_some();
+    let mut output = String::new();
+
+    for (row, line) in self.rows().enumerate() {
+      let mut previous = None;
+      for &cell in line {
+        if colored && previous != Some(cell) {
+          match (cell, options.alive_fg, options.dead_bg) {
+            (Cell::Alive, Some(fg), _) => output.push_str(&format!("\x1b[0;38;5;{}m", fg)),
+            (Cell::Dead, _, Some(bg)) => output.push_str(&format!("\x1b[0;48;5;{}m", bg)),
+            _ => output.push_str("\x1b[0m"),
+          }
+          previous = Some(cell);
+        }
+        output.push(match cell {
+          Cell::Alive => options.alive_glyph,
+          Cell::Dead => options.dead_glyph,
+        });
+      }
+
+      if colored {
+        output.push_str("\x1b[0m");
+      }
+      if options.trailing_newline || row + 1 < self.height as usize {
+        output.push('\n');
+      }
+    }
+    output
+  }
+
+  /// Create a universe from coordinates relative to its center.
+  ///
+  /// Each coordinate is a `(row, col)` offset from the center cell
+  /// `(height / 2, width / 2)`, so offsets may be negative. Coordinates
+  /// that fall outside of the universe are rejected rather than wrapped.
+  ///
+  /// Example:
+  ///
+  /// ```rust
+  /// use game_of_life::Universe;
+  /// // A vertical blinker in the middle of the universe.
+  /// let universe = Universe::from_coordinates(&[(0, 0), (1, 0), (-1, 0)], 5, 5).unwrap();
+  /// ```
+  pub fn from_coordinates(
+    coords: &[(i32, i32)],
+    width: u32,
+    height: u32,
+  ) -> Result<Universe, ParseError> {
+    let mut universe = Universe::dead(width, height);
+    let (center_row, center_col) = (i64::from(height / 2), i64::from(width / 2));
+
+    for &(delta_row, delta_col) in coords {
+      let row = center_row + i64::from(delta_row);
+      let col = center_col + i64::from(delta_col);
+      if row < 0 || row >= i64::from(height) || col < 0 || col >= i64::from(width) {
+        return Err(ParseError::OutOfBounds { row, col });
+      }
+
+      let idx = universe.get_index(row as u32, col as u32);
+      universe.cells[idx] = Cell::Alive;
+    }
+
+    Ok(universe)
+  }
+
+  /// Create a universe from a PNG, PGM or PPM image, sized to the image.
+  ///
+  /// Cells are alive where the luminance of their pixel, scaled to
+  /// `0..=255`, exceeds `threshold`. Color is converted with the Rec. 601
+  /// luma weights and alpha is ignored. Interlaced PNGs aren't supported,
+  /// and images of more than [`Universe::MAX_CELLS`] pixels are rejected.
+  #[cfg(feature = "image")]
+  pub fn from_image_luma(bytes: &[u8], threshold: u8) -> Result<Universe, ImageError> {
+    let image = image::decode_luma(bytes)?;
+    let mut universe = Universe::dead(image.width, image.height);
+    for (cell, &luma) in universe.cells.iter_mut().zip(image.pixels.iter()) {
+      if luma > threshold {
+        *cell = Cell::Alive;
+      }
+    }
+    Ok(universe)
+  }
+
   /// Get the dead and alive cells in the entire universe.
   pub fn get_cells(&self) -> &[Cell] {
     &self.cells
   }
 
+  /// Get the pixels drawn by the last [`Universe::render_rgba_buffer`].
+  pub fn get_rgba(&self) -> &[u8] {
+    &self.rgba
+  }
+
+  /// Render the universe like [`Universe::render_rgba`], into a
+  /// caller-owned buffer which can be reused across frames.
+  ///
+  /// The buffer needs 4 bytes per pixel, extra bytes are left untouched,
+  /// and a buffer which is too small is rejected. From JavaScript, use
+  /// [`Universe::render_rgba_buffer`] instead, which avoids copying the
+  /// pixels across the wasm boundary.
+  pub fn render_rgba_into(
+    &self,
+    buf: &mut [u8],
+    alive: u32,
+    dead: u32,
+  ) -> Result<(), BufferTooSmall> {
+    self.render_rgba_scaled_into(buf, alive, dead, self.cell_px)
+  }
+
+  /// Render the universe like [`Universe::render_rgba_into`], with each
+  /// cell drawn as a `cell_px` sized square instead of the stored size.
+  pub fn render_rgba_scaled_into(
+    &self,
+    buf: &mut [u8],
+    alive: u32,
+    dead: u32,
+    cell_px: u32,
+  ) -> Result<(), BufferTooSmall> {
+    let required = self.frame_len(cell_px);
+    if buf.len() < required {
+      return Err(BufferTooSmall {
+        required,
+        found: buf.len(),
+      });
+    }
+
+    let (alive, dead) = (alive.to_be_bytes(), dead.to_be_bytes());
+    let cell_px = clamp_cell_px(cell_px) as usize;
+    let mut pixels = buf.chunks_exact_mut(4);
+    for line in self.rows() {
+      for _ in 0..cell_px {
+        for &cell in line {
+          let color = if cell == Cell::Alive { &alive } else { &dead };
+          for pixel in pixels.by_ref().take(cell_px) {
+            pixel.copy_from_slice(color);
+          }
+        }
+      }
+    }
+    Ok(())
+  }
+
   /// Set cells to be alive in a universe by passing the row and column
   /// of each cell as an array.
   ///
@@ -162,7 +966,7 @@ impl Universe {
   /// ```rust
   /// use game_of_life::Universe;
   /// let mut universe = Universe::new(5, 5);
-  /// universe.set_cells(&[(1, 2), (2, 3), (3, 4), (4, 5)]);
+  /// universe.set_cells(&[(1, 2), (2, 3), (3, 4), (4, 0)]);
   /// ```
   pub fn set_cells(&mut self, cells: &[(u32, u32)]) {
     for (row, col) in cells.iter().cloned() {
@@ -170,23 +974,105 @@ impl Universe {
       self.cells[idx] = Cell::Alive;
     }
   }
-}
 
-/// Private methods.
-impl Universe {
-  /// The row and column are translated into an index into the
-  /// cells vector to access the cell at a given row and column.
-  fn get_index(&self, row: u32, column: u32) -> usize {
-    (row * self.width + column) as usize
+  /// Take a snapshot of the universe which can be restored later.
+  pub fn checkpoint(&self) -> Checkpoint {
+    Checkpoint {
+      width: self.width,
+      height: self.height,
+      cells: self.cells.clone(),
+      generation: self.generation,
+      transitions: self.transitions,
+    }
   }
 
-  /// Get the state of a cell at a given row and column.
+  /// Restore the universe to a previously taken checkpoint.
+  pub fn restore(&mut self, checkpoint: &Checkpoint) {
+    self.width = checkpoint.width;
+    self.height = checkpoint.height;
+    self.cells = checkpoint.cells.clone();
+    self.next = checkpoint.cells.clone();
+    self.generation = checkpoint.generation;
+    self.transitions = checkpoint.transitions;
+  }
+
+  /// Find the first generation, within `within` ticks, at which the
+  /// population equals or exceeds `target`.
   ///
-  /// Get the count of how many neighbors are alive,
-  /// to estimate the next state of the cell.
-  fn live_neighbor_count(&self, row: u32, column: u32) -> u8 {
-    let mut count = 0;
-    for delta_row in [self.height - 1, 0, 1].iter().cloned() {
+  /// Returns `Some(0)` if the current population already reaches the
+  /// target, and `None` if it is never reached. The universe is restored
+  /// to its original state afterwards.
+  pub fn reaches_population(&mut self, target: u32, within: u32) -> Option<u32> {
+    let checkpoint = self.checkpoint();
+
+    let mut reached = None;
+    for generation in 0..=within {
+      if generation > 0 {
+        self.tick();
+      }
+      if self.population() >= target {
+        reached = Some(generation);
+        break;
+      }
+    }
+
+    self.restore(&checkpoint);
+    reached
+  }
+
+  /// Encode the cells that differ between `self` and `other` as an RLE body.
+  ///
+  /// Toggled cells are encoded as `o` and unchanged ones as `b`, so
+  /// applying the diff to `self` with [`Universe::apply_diff_rle`] yields
+  /// `other`.
+  ///
+  /// Example:
+  ///
+  /// ```rust
+  /// use game_of_life::Universe;
+  /// let mut universe = Universe::new(5, 5);
+  /// let mut next = Universe::new(5, 5);
+  /// next.tick();
+  ///
+  /// let diff = universe.diff_rle(&next).unwrap();
+  /// universe.apply_diff_rle(&diff).unwrap();
+  /// assert_eq!(universe.get_cells(), next.get_cells());
+  /// ```
+  pub fn diff_rle(&self, other: &Universe) -> Result<String, DimensionMismatch> {
+    self.check_same_size(other)?;
+    let toggled: Vec<Cell> = self
+      .cells
+      .iter()
+      .zip(other.cells.iter())
+      .map(|(a, b)| if a == b { Cell::Dead } else { Cell::Alive })
+      .collect();
+    Ok(rle::encode(&toggled, self.width))
+  }
+
+  /// Toggle every cell marked in a diff produced by [`Universe::diff_rle`].
+  ///
+  /// The universe is left untouched if the diff is malformed.
+  pub fn apply_diff_rle(&mut self, diff: &str) -> Result<(), ParseError> {
+    for (row, col) in rle::decode(diff, self.width, self.height)? {
+      let idx = self.get_index(row, col);
+      self.cells[idx] = self.cells[idx].toggle();
+    }
+    Ok(())
+  }
+
+  /// Get the state of a cell at a given row and column.
+  ///
+  /// Get the count of how many neighbors are alive,
+  /// to estimate the next state of the cell.
+  ///
+  /// The neighbors counted depend on the [`Neighborhood`] of the universe.
+  pub fn live_neighbor_count(&self, row: u32, column: u32) -> u8 {
+    if self.neighborhood == Neighborhood::Hex {
+      return self.hex_neighbor_count(row, column);
+    }
+
+    let mut count = 0;
+    for delta_row in [self.height - 1, 0, 1].iter().cloned() {
       for delta_col in [self.width - 1, 0, 1].iter().cloned() {
         if delta_row == 0 && delta_col == 0 {
           continue;
@@ -200,16 +1086,1185 @@ impl Universe {
     }
     count
   }
+
+  /// Sum the states of the 3x3 neighborhood around a cell, each multiplied
+  /// by its weight in `kernel`.
+  ///
+  /// `kernel[1][1]` is the weight of the cell itself, `kernel[0][0]` the
+  /// weight of its top-left neighbor and so on. Neighbors wrap around the
+  /// edges of the universe, just like in [`Universe::live_neighbor_count`].
+  ///
+  /// Example:
+  ///
+  /// ```rust
+  /// use game_of_life::Universe;
+  /// let universe = Universe::new(5, 5);
+  /// let moore = [[1, 1, 1], [1, 0, 1], [1, 1, 1]];
+  /// assert_eq!(
+  ///   universe.weighted_neighbor_sum(2, 2, &moore),
+  ///   universe.live_neighbor_count(2, 2) as i32
+  /// );
+  /// ```
+  pub fn weighted_neighbor_sum(&self, row: u32, column: u32, kernel: &[[i32; 3]; 3]) -> i32 {
+    let mut sum = 0;
+    for (kernel_row, delta_row) in [self.height - 1, 0, 1].iter().cloned().enumerate() {
+      for (kernel_col, delta_col) in [self.width - 1, 0, 1].iter().cloned().enumerate() {
+        let neighbor_row = (row + delta_row) % self.height;
+        let neighbor_col = (column + delta_col) % self.width;
+        let idx = self.get_index(neighbor_row, neighbor_col);
+        sum += kernel[kernel_row][kernel_col] * self.cells[idx] as i32;
+      }
+    }
+    sum
+  }
+
+  /// Find the live cell closest to `(row, col)`.
+  ///
+  /// Distance is measured as the Chebyshev distance on the torus, so the
+  /// search wraps around the edges of the universe. Ties are broken by
+  /// scan order within the ring. Returns `None` if every cell is dead.
+  pub fn nearest_live(&self, row: u32, col: u32) -> Option<(u32, u32)> {
+    if self.width == 0 || self.height == 0 {
+      return None;
+    }
+    let max_radius = (self.width.max(self.height) / 2) as i64;
+    let (width, height) = (i64::from(self.width), i64::from(self.height));
+
+    for radius in 0..=max_radius {
+      for delta_row in -radius..=radius {
+        for delta_col in -radius..=radius {
+          // Only visit the ring at exactly `radius`, inner rings were
+          // already searched.
+          if delta_row.abs() != radius && delta_col.abs() != radius {
+            continue;
+          }
+
+          let r = (i64::from(row) + delta_row).rem_euclid(height) as u32;
+          let c = (i64::from(col) + delta_col).rem_euclid(width) as u32;
+          if self.cells[self.get_index(r, c)] == Cell::Alive {
+            return Some((r, c));
+          }
+        }
+      }
+    }
+    None
+  }
+
+  /// Serialize only the live cells of the universe.
+  ///
+  /// The layout is the width and height as little-endian `u32`s, followed
+  /// by the number of live cells and the gaps between their flat indices,
+  /// all as LEB128 varints. This is much smaller than a dense encoding
+  /// when few cells are alive.
+  pub fn to_sparse_bytes(&self) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&self.width.to_le_bytes());
+    bytes.extend_from_slice(&self.height.to_le_bytes());
+    utils::write_varint(&mut bytes, u64::from(self.population()));
+
+    let mut previous = 0;
+    for (idx, _) in self
+      .cells
+      .iter()
+      .enumerate()
+      .filter(|(_, &cell)| cell == Cell::Alive)
+    {
+      utils::write_varint(&mut bytes, (idx - previous) as u64);
+      previous = idx;
+    }
+    bytes
+  }
+
+  /// Deserialize a universe written by [`Universe::to_sparse_bytes`].
+  ///
+  /// Universes of more than [`Universe::MAX_CELLS`] cells are rejected.
+  pub fn from_sparse_bytes(bytes: &[u8]) -> Result<Universe, ParseError> {
+    if bytes.len() < 8 {
+      return Err(ParseError::UnexpectedEnd);
+    }
+    let width = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    let height = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+    let mut universe = Universe::checked_dead(width, height)?;
+
+    let mut offset = 8;
+    let count = utils::read_varint(bytes, &mut offset)?;
+    let mut idx = 0u64;
+    for _ in 0..count {
+      let start = offset;
+      idx = idx
+        .checked_add(utils::read_varint(bytes, &mut offset)?)
+        .ok_or(ParseError::InvalidVarint { offset: start })?;
+      if idx >= universe.cells.len() as u64 {
+        return Err(ParseError::OutOfBounds {
+          row: (idx / u64::from(width.max(1))) as i64,
+          col: (idx % u64::from(width.max(1))) as i64,
+        });
+      }
+      universe.cells[idx as usize] = Cell::Alive;
+    }
+
+    if offset != bytes.len() {
+      return Err(ParseError::TrailingData { offset });
+    }
+    Ok(universe)
+  }
+
+  /// Serialize the universe as compactly as possible for small patterns,
+  /// such as to share them in a QR code.
+  ///
+  /// The layout is the width and height, followed by the number of dead
+  /// cells before each live cell since the previous one, all as LEB128
+  /// varints. Unlike [`Universe::to_sparse_bytes`] there is no count of
+  /// live cells: they run to the end of the payload.
+  pub fn to_compact_payload(&self) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    utils::write_varint(&mut bytes, u64::from(self.width));
+    utils::write_varint(&mut bytes, u64::from(self.height));
+
+    let mut next = 0;
+    for (idx, _) in self
+      .cells
+      .iter()
+      .enumerate()
+      .filter(|(_, &cell)| cell == Cell::Alive)
+    {
+      utils::write_varint(&mut bytes, (idx - next) as u64);
+      next = idx + 1;
+    }
+    bytes
+  }
+
+  /// Deserialize a universe written by [`Universe::to_compact_payload`].
+  ///
+  /// Universes of more than [`Universe::MAX_CELLS`] cells are rejected.
+  pub fn from_compact_payload(bytes: &[u8]) -> Result<Universe, ParseError> {
+    let mut offset = 0;
+    let dimension = |offset: &mut usize| {
+      let start = *offset;
+      let value = utils::read_varint(bytes, offset)?;
+      u32::try_from(value).map_err(|_| ParseError::InvalidVarint { offset: start })
+    };
+    let width = dimension(&mut offset)?;
+    let height = dimension(&mut offset)?;
+    let mut universe = Universe::checked_dead(width, height)?;
+
+    let mut next = 0u64;
+    while offset < bytes.len() {
+      let idx = next.saturating_add(utils::read_varint(bytes, &mut offset)?);
+      if idx >= universe.cells.len() as u64 {
+        return Err(ParseError::OutOfBounds {
+          row: (idx / u64::from(width.max(1))) as i64,
+          col: (idx % u64::from(width.max(1))) as i64,
+        });
+      }
+      universe.cells[idx as usize] = Cell::Alive;
+      next = idx + 1;
+    }
+    Ok(universe)
+  }
+
+  /// Tick once, then let `inject` edit the universe.
+  ///
+  /// `inject` receives the universe and its new generation number, and
+  /// can add cells to model external input such as a rain of random
+  /// cells or periodic pattern injection.
+  pub fn tick_with_injection(&mut self, mut inject: impl FnMut(&mut Universe, u64)) {
+    self.tick();
+    let generation = self.generation;
+    inject(self, generation);
+  }
+
+  /// Area of the convex hull of the live cell centers.
+  ///
+  /// The hull is built with Andrew's monotone chain algorithm. Empty,
+  /// single-cell and collinear patterns have no area and return `0.0`.
+  /// The hull ignores wrapping, so patterns crossing an edge look spread
+  /// over the whole universe.
+  pub fn live_hull_area(&self) -> f64 {
+    // Cells are visited in scan order, which is already sorted by row
+    // then column as the monotone chain requires.
+    let points: Vec<(i64, i64)> = self
+      .cells
+      .iter()
+      .enumerate()
+      .filter(|(_, &cell)| cell == Cell::Alive)
+      .map(|(idx, _)| {
+        let idx = idx as i64;
+        let width = i64::from(self.width);
+        (idx / width, idx % width)
+      })
+      .collect();
+    if points.len() < 3 {
+      return 0.0;
+    }
+
+    let cross = |o: (i64, i64), a: (i64, i64), b: (i64, i64)| {
+      (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+    };
+    let mut hull: Vec<(i64, i64)> = Vec::with_capacity(points.len() * 2);
+    // Lower hull.
+    for &point in &points {
+      while hull.len() >= 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0 {
+        hull.pop();
+      }
+      hull.push(point);
+    }
+    // Upper hull, walking back from the last point.
+    let lower_len = hull.len() + 1;
+    for &point in points.iter().rev().skip(1) {
+      while hull.len() >= lower_len && cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0
+      {
+        hull.pop();
+      }
+      hull.push(point);
+    }
+    // The first point closes the upper hull and is already in the lower.
+    hull.pop();
+
+    let twice_area: i64 = (0..hull.len())
+      .map(|i| {
+        let (a, b) = (hull[i], hull[(i + 1) % hull.len()]);
+        a.0 * b.1 - b.0 * a.1
+      })
+      .sum();
+    twice_area.abs() as f64 / 2.0
+  }
+
+  /// Toggle the state of a cell and return its new state.
+  ///
+  /// Unlike [`Universe::toggle_cell`], coordinates outside of the universe
+  /// are rejected instead of wrapping around.
+  pub fn toggle_cell_checked(&mut self, row: u32, col: u32) -> Result<Cell, CoordError> {
+    let idx = self.checked_index(row, col)?;
+    self.cells[idx] = self.cells[idx].toggle();
+    Ok(self.cells[idx])
+  }
+
+  /// Get the `(row, col)` of every live cell, in scan order.
+  pub fn live_cells(&self) -> Vec<(u32, u32)> {
+    live_cells_of(&self.cells, self.width)
+  }
+
+  /// Get the smallest rectangle containing every live cell as
+  /// `(top, left, height, width)`, or `None` if every cell is dead.
+  ///
+  /// The rectangle ignores wrapping, so patterns crossing an edge span the
+  /// whole universe.
+  pub fn bounding_box(&self) -> Option<(u32, u32, u32, u32)> {
+    bounding_box_of(&self.live_cells())
+  }
+
+  /// Run the universe for up to `max` generations until it settles.
+  ///
+  /// After each tick the state is compared against every earlier one: an
+  /// identical state means the universe is still (period 1) or
+  /// oscillating, and identical live cells at another position mean it is
+  /// a spaceship. The universe is left at the generation where this was
+  /// detected.
+  pub fn stabilize(&mut self, max: u32) -> Stabilization {
+    /// What is remembered about the shape of every generation.
+    struct Seen {
+      shape_hash: u64,
+      shape: Vec<(u32, u32)>,
+      bbox: Option<(u32, u32, u32, u32)>,
+    }
+    let mut states = StateHistory::default();
+    let mut history: Vec<Seen> = Vec::new();
+
+    for generation in 0..=max {
+      if generation > 0 {
+        self.tick();
+      }
+      if let Some(start) = states.repeat_or_record(&self.cells) {
+        let (period, start) = (generation - start as u32, start as u32);
+        return if period == 1 {
+          Stabilization::Still(start)
+        } else {
+          Stabilization::Oscillator {
+            period,
+            generation: start,
+          }
+        };
+      }
+
+      let shape = self.live_coords_relative_to_bbox();
+      let shape_hash = hash_of(&shape);
+      let bbox = self.bounding_box();
+
+      // Hashes of the shape may collide, so the translation is only
+      // trusted once the shapes themselves match.
+      if let Some((top, left, _, _)) = bbox {
+        let translated = history
+          .iter()
+          .enumerate()
+          .find(|(_, seen)| seen.shape_hash == shape_hash && seen.shape == shape);
+        if let Some((start, seen)) = translated {
+          let (seen_top, seen_left, _, _) = seen.bbox.expect("the shape has live cells");
+          return Stabilization::Spaceship {
+            period: generation - start as u32,
+            displacement: (top as i32 - seen_top as i32, left as i32 - seen_left as i32),
+            generation: start as u32,
+          };
+        }
+      }
+
+      history.push(Seen {
+        shape_hash,
+        shape,
+        bbox,
+      });
+    }
+
+    Stabilization::Unresolved
+  }
+
+  /// Count the live cells within a Chebyshev `radius` of `(row, col)`.
+  ///
+  /// The square wraps around the edges of the universe, but a cell is
+  /// never counted twice when the square is larger than the universe. The
+  /// cell itself is only counted if `include_center` is set, so a radius of
+  /// 1 without the center matches [`Universe::live_neighbor_count`] on
+  /// universes of at least 3x3.
+  pub fn live_count_in_radius(&self, row: u32, col: u32, radius: u32, include_center: bool) -> u32 {
+    // Offsets from the center along one axis, each visited at most once.
+    let offsets = |size: u32| -> Vec<u32> {
+      if u64::from(radius) * 2 + 1 >= u64::from(size) {
+        (0..size).collect()
+      } else {
+        (size - radius..size).chain(0..=radius).collect()
+      }
+    };
+
+    let mut count = 0;
+    for delta_row in offsets(self.height) {
+      for delta_col in offsets(self.width) {
+        if delta_row == 0 && delta_col == 0 && !include_center {
+          continue;
+        }
+        let neighbor_row = (row + delta_row) % self.height;
+        let neighbor_col = (col + delta_col) % self.width;
+        count += self.cells[self.get_index(neighbor_row, neighbor_col)] as u32;
+      }
+    }
+    count
+  }
+
+  /// Get the change in population since `checkpoint` was taken.
+  ///
+  /// Positive when cells were born, negative when cells died.
+  pub fn population_delta_since(&self, checkpoint: &Checkpoint) -> i64 {
+    let before = checkpoint
+      .cells
+      .iter()
+      .filter(|&&cell| cell == Cell::Alive)
+      .count();
+    i64::from(self.population()) - before as i64
+  }
+
+  /// Render only the cells that differ from `prev` as ANSI escapes.
+  ///
+  /// Each changed cell is drawn with the same glyphs as
+  /// [`Universe::render`] after moving the cursor to it (1-based
+  /// `ESC[row;colH`), so a terminal showing `prev` ends up showing `self`
+  /// without being redrawn. Runs of changed cells on a line share one
+  /// cursor move. If the dimensions differ, the screen is cleared and the
+  /// whole universe is drawn instead.
+  pub fn render_diff_text(&self, prev: &Universe) -> String {
+    let mut output = String::new();
+
+    if (self.width, self.height) != (prev.width, prev.height) {
+      output.push_str("\x1b[2J");
+      for (row, line) in self.rows().enumerate() {
+        output.push_str(&format!("\x1b[{};1H", row + 1));
+        output.extend(line.iter().map(|&cell| glyph_of(cell)));
+      }
+      return output;
+    }
+
+    let mut cursor = None;
+    for (idx, (&cell, &before)) in self.cells.iter().zip(prev.cells.iter()).enumerate() {
+      if cell == before {
+        continue;
+      }
+      let (row, col) = (idx as u32 / self.width, idx as u32 % self.width);
+      if cursor != Some((row, col)) {
+        output.push_str(&format!("\x1b[{};{}H", row + 1, col + 1));
+      }
+      output.push(glyph_of(cell));
+      cursor = Some((row, col + 1));
+    }
+    output
+  }
+
+  /// Start tracking the total number of births and deaths over the
+  /// lifetime of the universe.
+  ///
+  /// Example:
+  ///
+  /// ```rust
+  /// use game_of_life::Universe;
+  /// let mut universe = Universe::new(8, 8).with_transition_counts();
+  /// universe.tick();
+  /// let (births, deaths) = universe.transition_counts().unwrap();
+  /// ```
+  pub fn with_transition_counts(mut self) -> Universe {
+    self.transitions.get_or_insert((0, 0));
+    self
+  }
+
+  /// Get the total `(births, deaths)` since tracking was enabled with
+  /// [`Universe::with_transition_counts`], or `None` if it is not.
+  pub fn transition_counts(&self) -> Option<(u64, u64)> {
+    self.transitions
+  }
+
+  /// Find the largest rectangle of dead cells as
+  /// `(top, left, height, width)`, or `None` if every cell is alive.
+  ///
+  /// Each row is treated as a histogram of how many dead cells are
+  /// stacked above it, and the largest rectangle under each histogram is
+  /// found with a stack in linear time. The rectangle does not wrap.
+  pub fn largest_empty_rect(&self) -> Option<(u32, u32, u32, u32)> {
+    let width = self.width as usize;
+    let mut heights = vec![0u32; width];
+    let mut best: Option<(u64, (u32, u32, u32, u32))> = None;
+
+    for row in 0..self.height {
+      for (col, height) in heights.iter_mut().enumerate() {
+        let cell = self.cells[self.get_index(row, col as u32)];
+        *height = if cell == Cell::Dead { *height + 1 } else { 0 };
+      }
+
+      // Columns with increasing heights; a sentinel of 0 at the end of
+      // the row flushes the stack.
+      let mut stack: Vec<usize> = Vec::with_capacity(width);
+      for col in 0..=width {
+        let current = heights.get(col).copied().unwrap_or(0);
+        while let Some(&top) = stack.last() {
+          if heights[top] < current {
+            break;
+          }
+          stack.pop();
+          let left = stack.last().map_or(0, |&idx| idx + 1);
+          let (height, span) = (heights[top], (col - left) as u32);
+          let area = u64::from(height) * u64::from(span);
+          if area > 0 && best.is_none_or(|(best_area, _)| area > best_area) {
+            best = Some((area, (row + 1 - height, left as u32, height, span)));
+          }
+        }
+        stack.push(col);
+      }
+    }
+
+    best.map(|(_, rect)| rect)
+  }
+
+  /// Get the rule of the universe, to inspect its birth and survival
+  /// counts.
+  pub fn rule(&self) -> &Rule {
+    &self.rule
+  }
+
+  /// Make a rectangle of the universe evolve by its own rule on `tick`.
+  ///
+  /// Neighbors are still counted across the edge of the rectangle, but
+  /// each cell applies the rule of the region it is in. The rectangle is
+  /// clipped to the universe, and when regions overlap the one added last
+  /// wins.
+  pub fn with_region_rule(&mut self, top: u32, left: u32, height: u32, width: u32, rule: Rule) {
+    self.regions.push(RegionRule {
+      top,
+      left,
+      height,
+      width,
+      rule,
+    });
+  }
+
+  /// Remove every region added with [`Universe::with_region_rule`].
+  pub fn clear_region_rules(&mut self) {
+    self.regions.clear();
+  }
+
+  /// Get the dead cells which will be born on the next tick, in scan
+  /// order, without advancing the universe.
+  pub fn would_be_born(&self) -> Vec<(u32, u32)> {
+    self.transitioning(Cell::Dead)
+  }
+
+  /// Get the live cells which will die on the next tick, in scan order,
+  /// without advancing the universe.
+  pub fn would_die(&self) -> Vec<(u32, u32)> {
+    self.transitioning(Cell::Alive)
+  }
+
+  /// Find the largest population within the next `within` generations,
+  /// as `(generation, population)`.
+  ///
+  /// The current generation is included, and the earliest generation
+  /// wins ties. The universe is restored to its original state afterwards.
+  pub fn peak_population(&mut self, within: u32) -> (u64, u32) {
+    let checkpoint = self.checkpoint();
+
+    let mut peak = (self.generation, self.population());
+    for _ in 0..within {
+      self.tick();
+      let population = self.population();
+      if population > peak.1 {
+        peak = (self.generation, population);
+      }
+    }
+
+    self.restore(&checkpoint);
+    peak
+  }
+
+  /// Get the live cells relative to the top-left corner of their bounding
+  /// box, in scan order.
+  ///
+  /// This normalizes the position of a pattern, so the same pattern
+  /// anywhere in the universe gives the same coordinates.
+  pub fn live_coords_relative_to_bbox(&self) -> Vec<(u32, u32)> {
+    let (top, left) = self
+      .bounding_box()
+      .map_or((0, 0), |(top, left, _, _)| (top, left));
+    self
+      .live_cells()
+      .into_iter()
+      .map(|(row, col)| (row - top, col - left))
+      .collect()
+  }
+
+  /// Get every symmetry the universe has, in the order of
+  /// [`Symmetry::ALL`].
+  pub fn symmetries(&self) -> Vec<Symmetry> {
+    Symmetry::ALL
+      .iter()
+      .cloned()
+      .filter(|symmetry| symmetry.fits(self.width, self.height))
+      .filter(|symmetry| {
+        (0..self.height).all(|row| {
+          (0..self.width).all(|col| {
+            let cell = self.cells[self.get_index(row, col)];
+            symmetry
+              .images(row, col, self.width, self.height)
+              .into_iter()
+              .all(|(r, c)| self.cells[self.get_index(r, c)] == cell)
+          })
+        })
+      })
+      .collect()
+  }
+
+  /// Resize the universe to the rectangle `height` x `width` starting at
+  /// `(top, left)`, discarding every cell outside of it.
+  ///
+  /// The rectangle is clipped to the universe. Region rules move with the
+  /// cells they cover, and those left outside of the rectangle are dropped.
+  pub fn crop(&mut self, top: u32, left: u32, height: u32, width: u32) {
+    let top = top.min(self.height);
+    let left = left.min(self.width);
+    let height = height.min(self.height - top);
+    let width = width.min(self.width - left);
+
+    let mut cropped = Vec::with_capacity((width * height) as usize);
+    for row in top..top + height {
+      let start = self.get_index(row, left);
+      cropped.extend_from_slice(&self.cells[start..start + width as usize]);
+    }
+
+    self.regions = self
+      .regions
+      .iter()
+      .filter_map(|region| {
+        let bottom = region.top.saturating_add(region.height).min(top + height);
+        let right = region.left.saturating_add(region.width).min(left + width);
+        let (region_top, region_left) = (region.top.max(top), region.left.max(left));
+        if region_top >= bottom || region_left >= right {
+          return None;
+        }
+        Some(RegionRule {
+          top: region_top - top,
+          left: region_left - left,
+          height: bottom - region_top,
+          width: right - region_left,
+          rule: region.rule,
+        })
+      })
+      .collect();
+
+    self.width = width;
+    self.height = height;
+    self.next = cropped.clone();
+    self.cells = cropped;
+  }
+
+  /// Count the live cells of the top-left, top-right, bottom-left and
+  /// bottom-right quarters of the universe, in that order.
+  ///
+  /// The top quarters have `height / 2` rows and the left quarters
+  /// `width / 2` columns, so with an odd size the middle row and column
+  /// belong to the bottom and right quarters.
+  pub fn population_by_quadrant(&self) -> [u32; 4] {
+    let (mid_row, mid_col) = (self.height / 2, self.width / 2);
+    let mut counts = [0; 4];
+    for (row, line) in self.rows().enumerate() {
+      for (col, &cell) in line.iter().enumerate() {
+        if cell == Cell::Alive {
+          let bottom = row as u32 >= mid_row;
+          let right = col as u32 >= mid_col;
+          counts[usize::from(bottom) * 2 + usize::from(right)] += 1;
+        }
+      }
+    }
+    counts
+  }
+
+  /// Set alive the cells of the pattern named `name`, with its top-left
+  /// corner at `(row, col)`.
+  ///
+  /// Patterns wrap around the edges of the universe like
+  /// [`Universe::toggle_cell`]. See
+  /// [`register_pattern`](crate::register_pattern) for the available
+  /// names.
+  pub fn stamp_named(&mut self, name: &str, row: u32, col: u32) -> Result<(), &'static str> {
+    let coords = patterns::lookup(name).ok_or("unknown pattern")?;
+    for (delta_row, delta_col) in coords {
+      let idx = self.get_index(
+        ((u64::from(row) + u64::from(delta_row)) % u64::from(self.height)) as u32,
+        ((u64::from(col) + u64::from(delta_col)) % u64::from(self.width)) as u32,
+      );
+      self.cells[idx] = Cell::Alive;
+    }
+    Ok(())
+  }
+
+  /// Advance the universe by one generation under `rule`, without
+  /// changing the rule it is stored with.
+  ///
+  /// `rule` applies to every cell, ignoring region rules for this step.
+  pub fn tick_with(&mut self, rule: &Rule) {
+    let stored = std::mem::replace(&mut self.rule, *rule);
+    let regions = std::mem::take(&mut self.regions);
+    self.tick();
+    self.rule = stored;
+    self.regions = regions;
+  }
+
+  /// Compute the Shannon entropy, in bits, of the `block` x `block`
+  /// sub-patterns of the universe.
+  ///
+  /// A window is taken at every cell, wrapping around the edges, so a
+  /// uniform universe has an entropy of 0 and the entropy is at most
+  /// `block * block` bits. A `block` of 0 has an entropy of 0, and a
+  /// `block` larger than the universe is clamped to its shorter side.
+  pub fn block_entropy(&self, block: u32) -> f64 {
+    let block = block.min(self.width.min(self.height));
+    if block == 0 {
+      return 0.0;
+    }
+
+    let mut counts: HashMap<Vec<Cell>, u32> = HashMap::new();
+    let mut window = Vec::with_capacity(block as usize * block as usize);
+    for row in 0..self.height {
+      for col in 0..self.width {
+        window.clear();
+        for delta_row in 0..block {
+          for delta_col in 0..block {
+            let idx = self.get_index(
+              (row + delta_row) % self.height,
+              (col + delta_col) % self.width,
+            );
+            window.push(self.cells[idx]);
+          }
+        }
+        *counts.entry(window.clone()).or_insert(0) += 1;
+      }
+    }
+
+    let total = f64::from(self.width * self.height);
+    counts
+      .values()
+      .map(|&count| {
+        let p = f64::from(count) / total;
+        -p * p.log2()
+      })
+      .sum()
+  }
+
+  /// Get the groups of live cells connected to each other through any of
+  /// their eight neighbors, each in scan order.
+  ///
+  /// Groups are ordered by their first cell. Like
+  /// [`Universe::bounding_box`], connections don't wrap around the edges.
+  pub fn components(&self) -> Vec<Vec<(u32, u32)>> {
+    let mut seen = vec![false; self.cells.len()];
+    let mut components = Vec::new();
+
+    for start in 0..self.cells.len() {
+      if seen[start] || self.cells[start] == Cell::Dead {
+        continue;
+      }
+      seen[start] = true;
+      let mut component = Vec::new();
+      let mut stack = vec![start];
+      while let Some(idx) = stack.pop() {
+        let (row, col) = (idx as u32 / self.width, idx as u32 % self.width);
+        component.push((row, col));
+        for neighbor_row in row.saturating_sub(1)..(row + 2).min(self.height) {
+          for neighbor_col in col.saturating_sub(1)..(col + 2).min(self.width) {
+            let neighbor = self.get_index(neighbor_row, neighbor_col);
+            if !seen[neighbor] && self.cells[neighbor] == Cell::Alive {
+              seen[neighbor] = true;
+              stack.push(neighbor);
+            }
+          }
+        }
+      }
+      component.sort_unstable();
+      components.push(component);
+    }
+    components
+  }
+
+  /// Get the bounding boxes, as `(top, left, height, width)`, of the
+  /// [components](Universe::components) which are back to their initial
+  /// state after `period` ticks but not after one.
+  ///
+  /// Each component is compared within its bounding box grown by one cell
+  /// on every side, which its phases may reach into. The universe is
+  /// ticked `period` times and then restored.
+  pub fn find_oscillators(&mut self, period: u32) -> Vec<(u32, u32, u32, u32)> {
+    if period == 0 {
+      return Vec::new();
+    }
+
+    let components = self.components();
+    let checkpoint = self.checkpoint();
+    self.tick();
+    let first = self.cells.clone();
+    self.tick_many(period - 1);
+    let last = std::mem::take(&mut self.cells);
+    self.restore(&checkpoint);
+
+    let (width, height) = (self.width, self.height);
+    let same_within = |a: &[Cell], b: &[Cell], (top, left, rows, cols): (u32, u32, u32, u32)| {
+      let (bottom, right) = ((top + rows + 1).min(height), (left + cols + 1).min(width));
+      (top.saturating_sub(1)..bottom).all(|row| {
+        (left.saturating_sub(1)..right).all(|col| {
+          let idx = (row * width + col) as usize;
+          a[idx] == b[idx]
+        })
+      })
+    };
+
+    components
+      .iter()
+      .filter_map(|component| bounding_box_of(component))
+      .filter(|&bbox| {
+        same_within(&self.cells, &last, bbox) && !same_within(&self.cells, &first, bbox)
+      })
+      .collect()
+  }
+
+  /// Count the cells live both in the universe and in `other`, placed with
+  /// its top-left corner at `(row_offset, col_offset)`.
+  ///
+  /// `other` wraps around the edges of the universe, like
+  /// [`Universe::stamp_named`], so a count of 0 means it can be stamped
+  /// there without colliding with anything.
+  pub fn overlap_count(&self, other: &Universe, row_offset: u32, col_offset: u32) -> u32 {
+    other
+      .live_cells()
+      .into_iter()
+      .filter(|&(row, col)| {
+        let idx = self.get_index(
+          ((u64::from(row) + u64::from(row_offset)) % u64::from(self.height)) as u32,
+          ((u64::from(col) + u64::from(col_offset)) % u64::from(self.width)) as u32,
+        );
+        self.cells[idx] == Cell::Alive
+      })
+      .count() as u32
+  }
+
+  /// Run the universe for up to `max` generations until it settles and
+  /// identify the objects left over, known as the ash of a soup.
+  ///
+  /// Objects are the groups of cells connected over a whole cycle, so
+  /// every phase of an oscillator is one object, and are ordered by their
+  /// first cell. Returns `None` if the universe didn't settle into still
+  /// lifes and oscillators within `max` generations, as escaping
+  /// spaceships never do. The universe is left where it settled.
+  pub fn ash(&mut self, max: u32) -> Option<Vec<PatternKind>> {
+    let period = match self.stabilize(max) {
+      Stabilization::Still(_) => 1,
+      Stabilization::Oscillator { period, .. } => period,
+      _ => return None,
+    };
+
+    // Every cell alive at some point of the cycle.
+    let checkpoint = self.checkpoint();
+    let mut phases = Vec::with_capacity(period as usize);
+    let mut ever_alive = self.cells.clone();
+    for _ in 0..period {
+      phases.push(self.cells.clone());
+      for (ever, &cell) in ever_alive.iter_mut().zip(self.cells.iter()) {
+        if cell == Cell::Alive {
+          *ever = Cell::Alive;
+        }
+      }
+      self.tick();
+    }
+    self.restore(&checkpoint);
+
+    let mut cycle = Universe::dead(self.width, self.height);
+    cycle.cells = ever_alive;
+    let ash = cycle
+      .components()
+      .into_iter()
+      .map(|object| {
+        let object_phases: Vec<Vec<(u32, u32)>> = phases
+          .iter()
+          .map(|phase| {
+            object
+              .iter()
+              .cloned()
+              .filter(|&(row, col)| phase[self.get_index(row, col)] == Cell::Alive)
+              .collect()
+          })
+          .collect();
+        analysis::classify(&object_phases)
+      })
+      .collect();
+    Some(ash)
+  }
+
+  /// Iterate over snapshots of the universe, ticking it between them,
+  /// until it repeats a state it already had.
+  ///
+  /// The first snapshot is the current generation. Repeats are detected
+  /// like in [`Universe::stabilize`], and iteration stops before yielding
+  /// a repeated state, so a still life yields one snapshot and a blinker
+  /// two, and the universe is left at the repeat. Universes which take
+  /// long to repeat may be bounded with [`Iterator::take`].
+  pub fn until_stable(&mut self) -> impl Iterator<Item = Checkpoint> + '_ {
+    let mut states = StateHistory::default();
+    let mut started = false;
+    std::iter::from_fn(move || {
+      if started {
+        self.tick();
+      }
+      started = true;
+      if states.repeat_or_record(&self.cells).is_some() {
+        return None;
+      }
+      Some(self.checkpoint())
+    })
+    .fuse()
+  }
+
+  /// Check whether the cell at `(row, col)` is in the given state.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the cell is outside of the universe, with the message of
+  /// the [`CoordError`].
+  pub fn cell_eq(&self, row: u32, col: u32, state: Cell) -> bool {
+    let idx = self
+      .checked_index(row, col)
+      .unwrap_or_else(|err| panic!("{}", err));
+    self.cells[idx] == state
+  }
+
+  /// Tick `steps` times, rendering every `render_every`-th generation with
+  /// [`Universe::render_rgba`], for a fast-forward which still shows
+  /// motion.
+  ///
+  /// Returns `steps / render_every` frames, the last one being the final
+  /// generation when `steps` is a multiple of `render_every`. A
+  /// `render_every` of 0 ticks without rendering anything.
+  pub fn tick_skip_render(
+    &mut self,
+    steps: u32,
+    render_every: u32,
+    alive: u32,
+    dead: u32,
+  ) -> Vec<Vec<u8>> {
+    let mut frames = Vec::with_capacity(steps.checked_div(render_every).unwrap_or(0) as usize);
+    for step in 1..=steps {
+      self.tick();
+      if render_every != 0 && step % render_every == 0 {
+        frames.push(self.render_rgba(alive, dead));
+      }
+    }
+    frames
+  }
+
+  /// Count the cells whose state differs from `other`, the Hamming
+  /// distance between the two universes.
+  pub fn distance(&self, other: &Universe) -> Result<u32, DimensionMismatch> {
+    self.check_same_size(other)?;
+    let differing = self
+      .cells
+      .iter()
+      .zip(other.cells.iter())
+      .filter(|(a, b)| a != b)
+      .count();
+    Ok(differing as u32)
+  }
+
+  /// Get the smallest rectangle, as `(top, left, height, width)`,
+  /// containing every live cell of the current generation and of the next
+  /// `steps` ones, such as to frame a recording.
+  ///
+  /// The universe is ticked `steps` times and then restored. Like
+  /// [`Universe::bounding_box`] the rectangle ignores wrapping, and it is
+  /// `(0, 0, 0, 0)` if every cell stays dead.
+  pub fn activity_bounds(&mut self, steps: u32) -> (u32, u32, u32, u32) {
+    let checkpoint = self.checkpoint();
+    let mut bounds: Option<(u32, u32, u32, u32)> = None;
+    for step in 0..=steps {
+      if step > 0 {
+        self.tick();
+      }
+      if let Some((top, left, height, width)) = self.bounding_box() {
+        let (bottom, right) = (top + height, left + width);
+        bounds = Some(match bounds {
+          Some((t, l, b, r)) => (t.min(top), l.min(left), b.max(bottom), r.max(right)),
+          None => (top, left, bottom, right),
+        });
+      }
+    }
+    self.restore(&checkpoint);
+    bounds.map_or((0, 0, 0, 0), |(top, left, bottom, right)| {
+      (top, left, bottom - top, right - left)
+    })
+  }
+
+  /// Count the ticks, up to `max`, until every cell of the universe is
+  /// dead, or `None` if some are still alive after `max` ticks.
+  ///
+  /// An empty universe is extinct after 0 ticks. The universe is restored
+  /// afterwards.
+  pub fn step_count_to_extinction(&mut self, max: u32) -> Option<u32> {
+    let checkpoint = self.checkpoint();
+    let mut extinction = None;
+    for step in 0..=max {
+      if step > 0 {
+        self.tick();
+      }
+      if self.population() == 0 {
+        extinction = Some(step);
+        break;
+      }
+    }
+    self.restore(&checkpoint);
+    extinction
+  }
+}
+
+/// Private methods.
+impl Universe {
+  /// Create a universe of the given size with every cell dead.
+  fn dead(width: u32, height: u32) -> Universe {
+    let cells = vec![Cell::Dead; (width * height) as usize];
+    let next = cells.clone();
+
+    Universe {
+      width,
+      height,
+      cells,
+      next,
+      generation: 0,
+      transitions: None,
+      rule: Rule::conway(),
+      regions: Vec::new(),
+      cell_px: 1,
+      rgba: Vec::new(),
+      neighborhood: Neighborhood::Moore,
+    }
+  }
+
+  /// The row and column are translated into an index into the
+  /// cells vector to access the cell at a given row and column.
+  fn get_index(&self, row: u32, column: u32) -> usize {
+    (row * self.width + column) as usize
+  }
+
+  /// Like [`Universe::get_index`], but rejects coordinates outside of the
+  /// universe.
+  fn checked_index(&self, row: u32, col: u32) -> Result<usize, CoordError> {
+    if row >= self.height || col >= self.width {
+      return Err(CoordError {
+        row,
+        col,
+        width: self.width,
+        height: self.height,
+      });
+    }
+    Ok(self.get_index(row, col))
+  }
+
+  /// Get the rule the cell at a given row and column evolves by.
+  fn rule_at(&self, row: u32, col: u32) -> Rule {
+    self
+      .regions
+      .iter()
+      .rev()
+      .find(|region| {
+        (region.top..region.top.saturating_add(region.height)).contains(&row)
+          && (region.left..region.left.saturating_add(region.width)).contains(&col)
+      })
+      .map_or(self.rule, |region| region.rule)
+  }
+
+  /// Number of bytes of an RGBA frame with `cell_px` sized cells.
+  ///
+  /// Saturates at `usize::MAX`, which no buffer can hold, rather than
+  /// overflowing.
+  fn frame_len(&self, cell_px: u32) -> usize {
+    let cell_px = clamp_cell_px(cell_px) as usize;
+    self.cells.len().saturating_mul(cell_px * cell_px * 4)
+  }
+
+  /// Get the state a cell will have after the next tick.
+  fn next_cell(&self, row: u32, col: u32) -> Cell {
+    let cell = self.cells[self.get_index(row, col)];
+    let live_neighbors = self.live_neighbor_count(row, col);
+    self.rule_at(row, col).next(cell, live_neighbors)
+  }
+
+  /// Get the cells currently in state `from` which will change state on
+  /// the next tick.
+  fn transitioning(&self, from: Cell) -> Vec<(u32, u32)> {
+    let mut cells = Vec::new();
+    for row in 0..self.height {
+      for col in 0..self.width {
+        if self.cells[self.get_index(row, col)] == from && self.next_cell(row, col) != from {
+          cells.push((row, col));
+        }
+      }
+    }
+    cells
+  }
+
+  /// Reject `other` unless it has the same size as the universe.
+  fn check_same_size(&self, other: &Universe) -> Result<(), DimensionMismatch> {
+    if (self.width, self.height) != (other.width, other.height) {
+      return Err(DimensionMismatch {
+        expected: (self.width, self.height),
+        found: (other.width, other.height),
+      });
+    }
+    Ok(())
+  }
+
+  /// Iterate over the rows of cells.
+  ///
+  /// A universe with no columns has no cells, so it has no rows either
+  /// rather than panicking on rows of zero length.
+  fn rows(&self) -> std::slice::Chunks<'_, Cell> {
+    self.cells.chunks(self.width.max(1) as usize)
+  }
+
+  /// Set the state of a cell, doing nothing if it falls outside of the
+  /// universe.
+  fn set_cell_clipped(&mut self, row: i64, col: i64, state: Cell) {
+    if (0..i64::from(self.height)).contains(&row) && (0..i64::from(self.width)).contains(&col) {
+      let idx = self.get_index(row as u32, col as u32);
+      self.cells[idx] = state;
+    }
+  }
+
+  /// Count the live neighbors of a cell on the hexagonal grid, see
+  /// [`Neighborhood::Hex`].
+  fn hex_neighbor_count(&self, row: u32, col: u32) -> u8 {
+    let offsets = if row.is_multiple_of(2) {
+      &neighborhood::HEX_EVEN_ROW
+    } else {
+      &neighborhood::HEX_ODD_ROW
+    };
+    let (height, width) = (i64::from(self.height), i64::from(self.width));
+    offsets
+      .iter()
+      .map(|&(delta_row, delta_col)| {
+        let neighbor_row = (i64::from(row) + i64::from(delta_row)).rem_euclid(height);
+        let neighbor_col = (i64::from(col) + i64::from(delta_col)).rem_euclid(width);
+        self.cells[self.get_index(neighbor_row as u32, neighbor_col as u32)] as u8
+      })
+      .sum()
+  }
+}
+
+/// Get the column the midpoint circle algorithm puts on row `y` of the
+/// first octant of a circle of `radius`, counted from the center.
+///
+/// The algorithm keeps the largest `x` with `x * (x - 1) < radius² - y²`,
+/// so it can be found directly instead of stepping through every row.
+fn midpoint_circle_x(radius: u32, y: u32) -> u32 {
+  let room = u128::from(radius)
+    .pow(2)
+    .saturating_sub(u128::from(y).pow(2));
+  if room == 0 {
+    return 0;
+  }
+  (4 * room - 3).isqrt().div_ceil(2) as u32
+}
+
+/// Clamp the size in pixels of a cell to `1..=`[`Universe::MAX_CELL_PX`].
+fn clamp_cell_px(cell_px: u32) -> u32 {
+  cell_px.clamp(1, Universe::MAX_CELL_PX)
+}
+
+/// Get the glyph a cell is drawn with by [`Universe::render`].
+fn glyph_of(cell: Cell) -> char {
+  if cell == Cell::Dead {
+    '◻'
+  } else {
+    '◼'
+  }
+}
+
+/// Count the live cells of `cells`.
+fn population_of(cells: &[Cell]) -> u32 {
+  cells.iter().filter(|&&cell| cell == Cell::Alive).count() as u32
+}
+
+/// Get the `(row, col)` coordinates of the live cells of `cells`, laid out
+/// in rows of `width`.
+fn live_cells_of(cells: &[Cell], width: u32) -> Vec<(u32, u32)> {
+  cells
+    .iter()
+    .enumerate()
+    .filter(|(_, &cell)| cell == Cell::Alive)
+    .map(|(idx, _)| (idx as u32 / width, idx as u32 % width))
+    .collect()
+}
+
+/// Get the smallest rectangle containing `cells`, given in scan order, as
+/// `(top, left, height, width)`.
+fn bounding_box_of(cells: &[(u32, u32)]) -> Option<(u32, u32, u32, u32)> {
+  let top = cells.first()?.0;
+  let bottom = cells.last()?.0;
+  let left = cells.iter().map(|&(_, col)| col).min()?;
+  let right = cells.iter().map(|&(_, col)| col).max()?;
+  Some((top, left, bottom - top + 1, right - left + 1))
+}
+
+/// Hash a value with the standard library's default hasher.
+fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+  let mut hasher = DefaultHasher::new();
+  value.hash(&mut hasher);
+  hasher.finish()
 }
 
 impl fmt::Display for Universe {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    for line in self.cells.as_slice().chunks(self.width as usize) {
+    for line in self.rows() {
       for &cell in line {
-        let symbol = if cell == Cell::Dead { '◻' } else { '◼' };
-        write!(f, "{}", symbol)?;
+        write!(f, "{}", glyph_of(cell))?;
       }
-      write!(f, "\n")?;
+      writeln!(f)?;
     }
 
     Ok(())
@@ -1,3 +1,5 @@
+use crate::{error::ParseError, universe::Universe};
+
 /// When the `console_error_panic_hook` feature is enabled, we can call the
 /// `set_panic_hook` function at least once during initialization, and then
 /// we will get better error messages if our code ever panics.
@@ -8,3 +10,98 @@ pub fn set_panic_hook() {
   #[cfg(feature = "console_error_panic_hook")]
   console_error_panic_hook::set_once();
 }
+
+/// Append `value` to `bytes` as an unsigned LEB128 varint.
+pub(crate) fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+  while value >= 0x80 {
+    bytes.push((value as u8 & 0x7f) | 0x80);
+    value >>= 7;
+  }
+  bytes.push(value as u8);
+}
+
+/// Read an unsigned LEB128 varint starting at `*offset`, advancing it.
+pub(crate) fn read_varint(bytes: &[u8], offset: &mut usize) -> Result<u64, ParseError> {
+  let start = *offset;
+  let mut value = 0u64;
+  for shift in (0..64).step_by(7) {
+    let byte = *bytes.get(*offset).ok_or(ParseError::UnexpectedEnd)?;
+    *offset += 1;
+    value |= u64::from(byte & 0x7f) << shift;
+    if byte & 0x80 == 0 {
+      return Ok(value);
+    }
+  }
+  Err(ParseError::InvalidVarint { offset: start })
+}
+
+/// A small, fast and deterministic pseudo-random number generator.
+///
+/// This is SplitMix64, which produces the same sequence on every platform
+/// for a given seed.
+pub(crate) struct Rng {
+  state: u64,
+}
+
+impl Rng {
+  /// Create a generator from a seed.
+  pub(crate) fn new(seed: u64) -> Rng {
+    Rng { state: seed }
+  }
+
+  /// Get the next 64 random bits.
+  pub(crate) fn next_u64(&mut self) -> u64 {
+    self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = self.state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+  }
+
+  /// Get a random number uniformly distributed in `[0, 1)`.
+  pub(crate) fn next_f64(&mut self) -> f64 {
+    (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+  }
+}
+
+/// Get a seed which differs between calls, from the current time.
+pub(crate) fn time_seed() -> u64 {
+  #[cfg(target_arch = "wasm32")]
+  {
+    // Milliseconds are too coarse on their own, so mix in a counter.
+    use std::sync::atomic::{AtomicU64, Ordering};
+    static CALLS: AtomicU64 = AtomicU64::new(0);
+    (js_sys::Date::now() as u64) ^ CALLS.fetch_add(1, Ordering::Relaxed).rotate_right(17)
+  }
+  #[cfg(not(target_arch = "wasm32"))]
+  {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map_or(0, |elapsed| elapsed.as_nanos() as u64)
+  }
+}
+
+/// Hash `bytes` into `hash` with 64-bit FNV-1a.
+///
+/// Unlike the standard library's hashers, FNV-1a is fully specified, so
+/// the same bytes hash to the same value on every platform and release.
+/// Start from [`FNV_OFFSET_BASIS`] and chain calls to hash several slices.
+pub(crate) fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+  bytes.iter().fold(hash, |hash, &byte| {
+    (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+  })
+}
+
+/// Initial value of an [`fnv1a`] hash.
+pub(crate) const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Get the number of cells of a `width` x `height` universe, or `None` if
+/// it overflows or exceeds [`Universe::MAX_CELLS`].
+pub(crate) fn cell_count(width: u32, height: u32) -> Option<usize> {
+  let count = (width as usize).checked_mul(height as usize)?;
+  if count > Universe::MAX_CELLS {
+    return None;
+  }
+  Some(count)
+}
@@ -0,0 +1,58 @@
+//! Ensure ticking the universe never touches the allocator.
+//!
+//! This lives in its own test binary because it installs a counting
+//! global allocator, which conflicts with the `wee_alloc` feature.
+
+#![cfg(not(feature = "wee_alloc"))]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use game_of_life::Universe;
+
+thread_local! {
+  static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Counts the allocations made on the current thread.
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    ALLOCATIONS.with(|count| count.set(count.get() + 1));
+    System.alloc(layout)
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    System.dealloc(ptr, layout)
+  }
+
+  unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+    ALLOCATIONS.with(|count| count.set(count.get() + 1));
+    System.realloc(ptr, layout, new_size)
+  }
+}
+
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+  ALLOCATIONS.with(|count| count.get())
+}
+
+#[test]
+pub fn test_tick_in_place_no_alloc() {
+  let mut universe = Universe::new(64, 64);
+
+  let before = allocations();
+  for _ in 0..1_000 {
+    universe.tick_in_place_no_alloc();
+  }
+  assert_eq!(allocations() - before, 0);
+
+  let before = allocations();
+  for _ in 0..1_000 {
+    universe.tick();
+  }
+  assert_eq!(allocations() - before, 0);
+}
@@ -0,0 +1,288 @@
+//! Test suite for seeding universes from images.
+
+#![cfg(feature = "image")]
+
+use game_of_life::{Cell, ImageError, Universe};
+
+#[test]
+pub fn test_from_image_luma_pgm() {
+  // A 3x2 binary graymap: bright, dark, bright / dark, bright, dark.
+  let mut image = b"P5\n# tiny test image\n3 2\n255\n".to_vec();
+  image.extend_from_slice(&[250, 10, 200, 0, 129, 128]);
+
+  let universe = Universe::from_image_luma(&image, 128).unwrap();
+  assert_eq!((universe.width(), universe.height()), (3, 2));
+  assert_eq!(universe.live_cells(), vec![(0, 0), (0, 2), (1, 1)]);
+}
+
+#[test]
+pub fn test_from_image_luma_ppm() {
+  // Pure green is brighter than pure red, which is brighter than blue.
+  let image = b"P3 3 1 15\n0 15 0  15 0 0  0 0 15\n";
+  let universe = Universe::from_image_luma(image, 100).unwrap();
+  assert_eq!(universe.get_cells(), &[Cell::Alive, Cell::Dead, Cell::Dead]);
+
+  let universe = Universe::from_image_luma(image, 50).unwrap();
+  assert_eq!(
+    universe.get_cells(),
+    &[Cell::Alive, Cell::Alive, Cell::Dead]
+  );
+}
+
+#[test]
+pub fn test_from_image_luma_netpbm_errors() {
+  assert_eq!(
+    Universe::from_image_luma(b"GIF89a", 128).err(),
+    Some(ImageError::UnsupportedFormat)
+  );
+  assert_eq!(
+    Universe::from_image_luma(b"P2 2 2 255 0 0 0", 128).err(),
+    Some(ImageError::UnexpectedEnd)
+  );
+  assert_eq!(
+    Universe::from_image_luma(b"P2 1 1 15 16", 128).err(),
+    Some(ImageError::InvalidSample(16))
+  );
+}
+
+#[test]
+pub fn test_from_image_luma_netpbm_huge_header() {
+  // 70000 * 70000 overflows a u32.
+  assert_eq!(
+    Universe::from_image_luma(b"P5 70000 70000 255\n\0\0\0\0", 128).err(),
+    Some(ImageError::InvalidHeader)
+  );
+  assert_eq!(
+    Universe::from_image_luma(b"P5 5000 5000 255\n\0\0\0\0", 128).err(),
+    Some(ImageError::InvalidHeader)
+  );
+
+  // A header fitting the limits doesn't reserve more than the data holds.
+  assert_eq!(
+    Universe::from_image_luma(b"P6 4000 4000 65535\n\0\0\0\0", 128).err(),
+    Some(ImageError::UnexpectedEnd)
+  );
+}
+
+/// Build a PNG image holding `rows`, each starting with its filter byte,
+/// stored without compression.
+fn png(width: u32, height: u32, depth: u8, color_type: u8, palette: &[u8], rows: &[u8]) -> Vec<u8> {
+  fn crc(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+      crc ^= u32::from(byte);
+      for _ in 0..8 {
+        crc = if crc & 1 == 1 {
+          crc >> 1 ^ 0xedb8_8320
+        } else {
+          crc >> 1
+        };
+      }
+    }
+    !crc
+  }
+  fn chunk(image: &mut Vec<u8>, kind: &[u8], data: &[u8]) {
+    image.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = image.len();
+    image.extend_from_slice(kind);
+    image.extend_from_slice(data);
+    let crc = crc(&image[start..]);
+    image.extend_from_slice(&crc.to_be_bytes());
+  }
+
+  let mut header = width.to_be_bytes().to_vec();
+  header.extend_from_slice(&height.to_be_bytes());
+  header.extend_from_slice(&[depth, color_type, 0, 0, 0]);
+
+  // A zlib stream of a single stored block, and its Adler-32 checksum.
+  let mut data = vec![0x78, 0x01, 0x01];
+  data.extend_from_slice(&(rows.len() as u16).to_le_bytes());
+  data.extend_from_slice(&(!(rows.len() as u16)).to_le_bytes());
+  data.extend_from_slice(rows);
+  let (low, high) = rows.iter().fold((1u32, 0u32), |(low, high), &byte| {
+    let low = (low + u32::from(byte)) % 65521;
+    (low, (high + low) % 65521)
+  });
+  data.extend_from_slice(&(high << 16 | low).to_be_bytes());
+
+  let mut image = b"\x89PNG\r\n\x1a\n".to_vec();
+  chunk(&mut image, b"IHDR", &header);
+  if !palette.is_empty() {
+    chunk(&mut image, b"PLTE", palette);
+  }
+  chunk(&mut image, b"IDAT", &data);
+  chunk(&mut image, b"IEND", &[]);
+  image
+}
+
+/// Get the cells `art` draws with `#` for alive and `.` for dead.
+fn cells_of(art: &str) -> Vec<Cell> {
+  art
+    .chars()
+    .filter(|glyph| !glyph.is_whitespace())
+    .map(|glyph| {
+      if glyph == '#' {
+        Cell::Alive
+      } else {
+        Cell::Dead
+      }
+    })
+    .collect()
+}
+
+#[test]
+pub fn test_from_image_luma_png() {
+  // A 16x16 graymap of four gliders, compressed by zlib with fixed Huffman
+  // codes.
+  let image = [
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x10, 0x00, 0x00, 0x00, 0x10, 0x08, 0x00, 0x00, 0x00, 0x00, 0x3a, 0x98, 0xa0,
+    0xbd, 0x00, 0x00, 0x00, 0x23, 0x49, 0x44, 0x41, 0x54, 0x78, 0xda, 0x63, 0x90, 0x3b, 0x21, 0x07,
+    0x01, 0x50, 0x9a, 0x41, 0x0e, 0x4d, 0x84, 0xe1, 0xc4, 0x09, 0x08, 0x03, 0x46, 0x33, 0xc8, 0xa1,
+    0x01, 0xea, 0x08, 0x0c, 0x0e, 0x77, 0x00, 0x00, 0xf9, 0x68, 0x2b, 0x49, 0x46, 0xc3, 0xa7, 0x38,
+    0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+  ];
+  let universe = Universe::from_image_luma(&image, 100).unwrap();
+  assert_eq!((universe.width(), universe.height()), (16, 16));
+  let glider = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+  let mut expected: Vec<(u32, u32)> = [(0, 0), (0, 8), (8, 0), (8, 8)]
+    .iter()
+    .flat_map(|&(top, left)| {
+      glider
+        .iter()
+        .map(move |&(row, col)| (top + row, left + col))
+    })
+    .collect();
+  expected.sort_unstable();
+  assert_eq!(universe.live_cells(), expected);
+
+  // An 8x8 graymap of white, gray and black pixels, compressed by zlib with
+  // its own Huffman codes.
+  let image = [
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x08, 0x08, 0x00, 0x00, 0x00, 0x00, 0xe1, 0x64, 0xe1,
+    0x57, 0x00, 0x00, 0x00, 0x25, 0x49, 0x44, 0x41, 0x54, 0x78, 0xda, 0x35, 0x8a, 0x41, 0x0e, 0x00,
+    0x00, 0x0c, 0xc1, 0xfa, 0x74, 0x3f, 0xb7, 0x49, 0x40, 0x1c, 0x14, 0x06, 0x5b, 0x80, 0xde, 0x09,
+    0x19, 0x88, 0x69, 0x4f, 0x2a, 0x2e, 0x92, 0x77, 0x7d, 0x1d, 0xbf, 0x41, 0x12, 0x72, 0x0d, 0x1f,
+    0x75, 0xb7, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+  ];
+  let white = "
+    #..##...
+    ........
+    ..##.#.#
+    ..#.#...
+    ........
+    .......#
+    ..#..#..
+    ##.#....
+  ";
+  let gray = "
+    .....#..
+    #.#..#..
+    ........
+    ........
+    .......#
+    #.......
+    ....#...
+    ........
+  ";
+  let universe = Universe::from_image_luma(&image, 200).unwrap();
+  assert_eq!(universe.get_cells(), cells_of(white).as_slice());
+  let universe = Universe::from_image_luma(&image, 100).unwrap();
+  let either: Vec<Cell> = cells_of(white)
+    .into_iter()
+    .zip(cells_of(gray))
+    .map(|(white, gray)| if white == Cell::Alive { white } else { gray })
+    .collect();
+  assert_eq!(universe.get_cells(), either.as_slice());
+}
+
+#[test]
+pub fn test_from_image_luma_png_filters() {
+  // Rows filtered by their left neighbor (wrapping past 255), the row
+  // above, their average, and the Paeth predictor, decoding to:
+  //   200   0   0
+  //     0   0 200
+  //   150  75 137
+  //   150  85 137
+  let rows = [1, 200, 56, 0, 2, 56, 0, 200, 3, 150, 0, 0, 4, 0, 10, 0];
+  let image = png(3, 4, 8, 0, &[], &rows);
+  let universe = Universe::from_image_luma(&image, 100).unwrap();
+  assert_eq!(universe.get_cells(), cells_of("#.. ..# #.# #.#").as_slice());
+  let universe = Universe::from_image_luma(&image, 140).unwrap();
+  assert_eq!(universe.get_cells(), cells_of("#.. ..# #.. #..").as_slice());
+}
+
+#[test]
+pub fn test_from_image_luma_png_pixel_formats() {
+  // Pure green is brighter than pure red, which is brighter than blue.
+  let image = png(3, 1, 8, 2, &[], &[0, 0, 255, 0, 255, 0, 0, 0, 0, 255]);
+  let universe = Universe::from_image_luma(&image, 100).unwrap();
+  assert_eq!(universe.get_cells(), cells_of("#..").as_slice());
+  let universe = Universe::from_image_luma(&image, 50).unwrap();
+  assert_eq!(universe.get_cells(), cells_of("##.").as_slice());
+
+  // 1-bit palette indices, packed from the most significant bit.
+  let image = png(5, 1, 1, 3, &[0, 0, 0, 255, 255, 255], &[0, 0b1011_0000]);
+  let universe = Universe::from_image_luma(&image, 128).unwrap();
+  assert_eq!(universe.get_cells(), cells_of("#.##.").as_slice());
+
+  // 16-bit samples are scaled down to 8 bits, and alpha is ignored.
+  let image = png(
+    2,
+    1,
+    16,
+    4,
+    &[],
+    &[0, 0x80, 0x00, 0xff, 0xff, 0xff, 0xff, 0, 0],
+  );
+  let universe = Universe::from_image_luma(&image, 127).unwrap();
+  assert_eq!(universe.get_cells(), cells_of(".#").as_slice());
+}
+
+#[test]
+pub fn test_from_image_luma_png_errors() {
+  // Interlaced images aren't supported.
+  let mut image = png(1, 1, 8, 0, &[], &[0, 0]);
+  image[28] = 1;
+  assert_eq!(
+    Universe::from_image_luma(&image, 128).err(),
+    Some(ImageError::UnsupportedFormat)
+  );
+
+  // RGB images have no 4-bit depth.
+  assert_eq!(
+    Universe::from_image_luma(&png(1, 1, 4, 2, &[], &[0, 0]), 128).err(),
+    Some(ImageError::InvalidHeader)
+  );
+  assert_eq!(
+    Universe::from_image_luma(&png(70000, 70000, 8, 0, &[], &[]), 128).err(),
+    Some(ImageError::InvalidHeader)
+  );
+
+  // The second pixel's index is past the single-color palette.
+  assert_eq!(
+    Universe::from_image_luma(&png(2, 1, 1, 3, &[0, 0, 0], &[0, 0b0100_0000]), 128).err(),
+    Some(ImageError::InvalidSample(1))
+  );
+
+  // Pixel data may neither stop short of the image nor run past it.
+  assert_eq!(
+    Universe::from_image_luma(&png(2, 1, 8, 0, &[], &[0, 0]), 128).err(),
+    Some(ImageError::UnexpectedEnd)
+  );
+  assert_eq!(
+    Universe::from_image_luma(&png(1, 1, 8, 0, &[], &[0, 0, 0]), 128).err(),
+    Some(ImageError::InvalidData)
+  );
+  assert_eq!(
+    Universe::from_image_luma(&png(1, 1, 8, 0, &[], &[5, 0]), 128).err(),
+    Some(ImageError::InvalidData)
+  );
+
+  let image = png(4, 4, 8, 0, &[], &[0; 20]);
+  assert_eq!(
+    Universe::from_image_luma(&image[..image.len() - 20], 128).err(),
+    Some(ImageError::UnexpectedEnd)
+  );
+}
@@ -0,0 +1,291 @@
+//! Test suite for the text and raster renderers.
+
+use game_of_life::{BufferTooSmall, RenderOptionsError, TextRenderOptions, Universe};
+
+pub fn blinker() -> Universe {
+  let mut universe = Universe::new(5, 5);
+  universe.set_width(5);
+  universe.set_height(5);
+  universe.set_cells(&[(2, 1), (2, 2), (2, 3)]);
+  universe
+}
+
+#[test]
+pub fn test_render_ansi_color() {
+  let universe = blinker();
+  let output = universe.render_ansi_color(196, 236);
+
+  // Every cell switch emits a reset followed by the chosen color.
+  assert!(output.contains("\x1b[0;38;5;196m◼◼◼"));
+  assert!(output.contains("\x1b[0;48;5;236m     "));
+
+  // Each line is terminated by a reset so colors never bleed.
+  let lines: Vec<&str> = output.lines().collect();
+  assert_eq!(lines.len(), 5);
+  assert!(lines.iter().all(|line| line.ends_with("\x1b[0m")));
+  assert!(output.ends_with("\x1b[0m\n"));
+}
+
+#[test]
+pub fn test_text_render_options_builder() {
+  assert_eq!(
+    TextRenderOptions::builder()
+      .alive_glyph('#')
+      .dead_glyph('#')
+      .build(),
+    Err(RenderOptionsError::SameGlyphs('#'))
+  );
+  assert_eq!(
+    TextRenderOptions::builder().dead_glyph('\n').build(),
+    Err(RenderOptionsError::ControlGlyph('\n'))
+  );
+
+  let universe = blinker();
+  let default = TextRenderOptions::builder().build().unwrap();
+  assert_eq!(universe.render_with(&default), universe.render());
+
+  let options = TextRenderOptions::builder()
+    .alive_glyph('#')
+    .dead_glyph('.')
+    .trailing_newline(false)
+    .build()
+    .unwrap();
+  assert_eq!(
+    universe.render_with(&options),
+    ".....\n.....\n.###.\n.....\n....."
+  );
+}
+
+#[test]
+pub fn test_render_diff_text() {
+  let prev = blinker();
+  let mut universe = blinker();
+  universe.tick();
+
+  // The center cell is unchanged, the four around it toggle.
+  assert_eq!(
+    universe.render_diff_text(&prev),
+    "\x1b[2;3H◼\x1b[3;2H◻\x1b[3;4H◻\x1b[4;3H◼"
+  );
+  assert_eq!(universe.render_diff_text(&universe), "");
+
+  // Adjacent changes share a single cursor move.
+  let mut wide = blinker();
+  wide.set_cells(&[(0, 0), (0, 1), (0, 2)]);
+  assert_eq!(wide.render_diff_text(&prev), "\x1b[1;1H◼◼◼");
+}
+
+#[test]
+pub fn test_render_rgba_into() {
+  let universe = blinker();
+  let (alive, dead) = (0x1122_33ff, 0xffff_ff00);
+
+  let mut small = vec![0; 99];
+  assert_eq!(
+    universe.render_rgba_into(&mut small, alive, dead),
+    Err(BufferTooSmall {
+      required: 100,
+      found: 99
+    })
+  );
+
+  // Bytes past the frame are left untouched.
+  let mut buf = vec![7; 104];
+  universe.render_rgba_into(&mut buf, alive, dead).unwrap();
+  assert_eq!(&buf[..4], &[0xff, 0xff, 0xff, 0x00]);
+  let center = (2 * 5 + 2) * 4;
+  assert_eq!(&buf[center..center + 4], &[0x11, 0x22, 0x33, 0xff]);
+  assert_eq!(&buf[100..], &[7, 7, 7, 7]);
+  assert_eq!(&buf[..100], universe.render_rgba(alive, dead).as_slice());
+
+  let lit = buf[..100]
+    .chunks(4)
+    .filter(|pixel| pixel[3] == 0xff)
+    .count();
+  assert_eq!(lit, 3);
+}
+
+#[test]
+pub fn test_render_rgba_buffer() {
+  let mut universe = blinker();
+  let (alive, dead) = (0x1122_33ff, 0xffff_ff00);
+  assert_eq!(universe.rgba_len(), 0);
+
+  universe.render_rgba_buffer(alive, dead);
+  assert_eq!(universe.rgba_len(), 100);
+  assert_eq!(
+    universe.get_rgba(),
+    universe.render_rgba(alive, dead).as_slice()
+  );
+
+  // Frames of the same size reuse the buffer in place.
+  let ptr = universe.rgba_ptr();
+  universe.tick();
+  universe.render_rgba_buffer(alive, dead);
+  assert_eq!(universe.rgba_ptr(), ptr);
+  assert_eq!(
+    universe.get_rgba(),
+    universe.render_rgba(alive, dead).as_slice()
+  );
+
+  universe.set_cell_px(2);
+  universe.render_rgba_buffer(alive, dead);
+  assert_eq!(universe.rgba_len(), 400);
+  assert_eq!(
+    universe.get_rgba(),
+    universe.render_rgba(alive, dead).as_slice()
+  );
+}
+
+#[test]
+pub fn test_tick_and_return_rgba() {
+  let mut fused = blinker();
+  let mut separate = blinker();
+
+  for _ in 0..3 {
+    let frame = fused.tick_and_return_rgba(0xffff_ffff, 0x0000_00ff);
+    separate.tick();
+    assert_eq!(frame, separate.render_rgba(0xffff_ffff, 0x0000_00ff));
+  }
+  assert_eq!(fused.generation(), 3);
+}
+
+#[test]
+pub fn test_cell_px() {
+  let mut universe = blinker();
+  assert_eq!(universe.cell_px(), 1);
+  assert_eq!(universe.render_rgba(0xffff_ffff, 0).len(), 5 * 5 * 4);
+
+  universe.set_cell_px(3);
+  assert_eq!(universe.cell_px(), 3);
+  let frame = universe.render_rgba(0xffff_ffff, 0);
+  assert_eq!(frame.len(), 15 * 15 * 4);
+  assert_eq!(frame, universe.render_rgba_scaled(0xffff_ffff, 0, 3));
+
+  // The blinker covers pixel rows 6..9 and columns 3..12.
+  let lit: Vec<(usize, usize)> = frame
+    .chunks(4)
+    .enumerate()
+    .filter(|(_, pixel)| pixel[0] == 0xff)
+    .map(|(idx, _)| (idx / 15, idx % 15))
+    .collect();
+  let expected: Vec<(usize, usize)> = (6..9)
+    .flat_map(|row| (3..12).map(move |col| (row, col)))
+    .collect();
+  assert_eq!(lit, expected);
+
+  // The stored size also sizes the buffer of the reusable renderer.
+  let mut buf = vec![0; 5 * 5 * 4];
+  assert_eq!(
+    universe.render_rgba_into(&mut buf, 0xffff_ffff, 0),
+    Err(BufferTooSmall {
+      required: 15 * 15 * 4,
+      found: 5 * 5 * 4
+    })
+  );
+  assert!(universe
+    .render_rgba_scaled_into(&mut buf, 0xffff_ffff, 0, 1)
+    .is_ok());
+
+  universe.set_cell_px(0);
+  assert_eq!(universe.cell_px(), 1);
+
+  // Huge sizes are clamped instead of overflowing the frame size.
+  universe.set_cell_px(u32::MAX);
+  assert_eq!(universe.cell_px(), Universe::MAX_CELL_PX);
+  let max = Universe::MAX_CELL_PX as usize;
+  assert_eq!(
+    universe.render_rgba(0xffff_ffff, 0).len(),
+    25 * max * max * 4
+  );
+  assert_eq!(
+    universe.render_rgba_scaled(0xffff_ffff, 0, u32::MAX),
+    universe.render_rgba(0xffff_ffff, 0)
+  );
+}
+
+#[test]
+pub fn test_tick_many_to_buffer() {
+  let mut batched = Universe::soup(24, 16, 5, 0.35);
+  let mut separate = Universe::soup(24, 16, 5, 0.35);
+
+  let frame = batched.tick_many_to_buffer(7, 0xffff_ffff, 0x0000_00ff);
+  for _ in 0..7 {
+    separate.tick();
+  }
+  assert_eq!(frame, separate.render_rgba(0xffff_ffff, 0x0000_00ff));
+  assert_eq!(batched.generation(), 7);
+
+  // No generations only renders.
+  assert_eq!(
+    batched.tick_many_to_buffer(0, 0xffff_ffff, 0x0000_00ff),
+    frame
+  );
+  batched.tick_many(3);
+  assert_eq!(batched.generation(), 10);
+}
+
+#[test]
+pub fn test_tick_skip_render() {
+  let mut skipping = Universe::soup(16, 16, 3, 0.4);
+  let mut separate = Universe::soup(16, 16, 3, 0.4);
+
+  let frames = skipping.tick_skip_render(10, 3, 0xffff_ffff, 0x0000_00ff);
+  assert_eq!(frames.len(), 10 / 3);
+  assert_eq!(skipping.generation(), 10);
+
+  // The frames are generations 3, 6 and 9.
+  for frame in &frames {
+    separate.tick_many(3);
+    assert_eq!(frame, &separate.render_rgba(0xffff_ffff, 0x0000_00ff));
+  }
+
+  assert_eq!(skipping.tick_skip_render(8, 4, 0xffff_ffff, 0).len(), 2);
+  assert!(skipping.tick_skip_render(5, 0, 0xffff_ffff, 0).is_empty());
+  assert_eq!(skipping.generation(), 23);
+}
+
+#[test]
+pub fn test_to_pbm() {
+  let pbm = blinker().to_pbm();
+  let mut lines = pbm.lines();
+  assert_eq!(lines.next(), Some("P1"));
+  assert_eq!(lines.next(), Some("5 5"));
+  assert_eq!(
+    lines.collect::<Vec<_>>(),
+    vec![
+      "0 0 0 0 0",
+      "0 0 0 0 0",
+      "0 1 1 1 0",
+      "0 0 0 0 0",
+      "0 0 0 0 0"
+    ]
+  );
+
+  // Wide rows are wrapped, and every bit matches its cell.
+  let soup = Universe::soup(40, 3, 8, 0.5);
+  let pbm = soup.to_pbm();
+  assert!(pbm.starts_with("P1\n40 3\n"));
+  assert!(pbm.lines().all(|line| line.len() <= 70));
+  let bits: Vec<u8> = pbm
+    .lines()
+    .skip(2)
+    .flat_map(|line| line.split(' '))
+    .map(|bit| bit.parse().unwrap())
+    .collect();
+  let cells: Vec<u8> = soup.get_cells().iter().map(|&cell| cell as u8).collect();
+  assert_eq!(bits, cells);
+
+  // The stored cell size scales the image.
+  let mut universe = blinker();
+  universe.set_cell_px(2);
+  let pbm = universe.to_pbm();
+  let mut lines = pbm.lines();
+  assert_eq!(lines.next(), Some("P1"));
+  assert_eq!(lines.next(), Some("10 10"));
+  let rows: Vec<&str> = lines.collect();
+  assert_eq!(rows.len(), 10);
+  assert_eq!(rows[3], "0 0 0 0 0 0 0 0 0 0");
+  assert_eq!(rows[4], "0 0 1 1 1 1 1 1 0 0");
+  assert_eq!(rows[5], rows[4]);
+}
@@ -0,0 +1,224 @@
+//! Test suite for life-like rules.
+
+use game_of_life::{reference_digest, rule_divergence, Neighborhood, Rule, RuleError, Universe};
+
+/// Create a universe of the given size with only the given cells alive.
+fn universe_with(width: u32, height: u32, cells: &[(u32, u32)]) -> Universe {
+  let mut universe = Universe::new(width, height);
+  universe.set_width(width);
+  universe.set_height(height);
+  universe.set_cells(cells);
+  universe
+}
+
+#[test]
+pub fn test_rule_sets() {
+  let conway: Rule = "B3/S23".parse().unwrap();
+  assert_eq!(conway.birth(), &[3]);
+  assert_eq!(conway.survival(), &[2, 3]);
+  assert_eq!(conway, Rule::conway());
+  assert_eq!(conway.to_string(), "B3/S23");
+
+  // Case, order and duplicates don't matter.
+  let seeds: Rule = "s/b2".parse().unwrap();
+  assert_eq!(seeds.birth(), &[2]);
+  assert!(seeds.survival().is_empty());
+  assert_eq!(
+    "B632/S3223".parse::<Rule>().unwrap().to_string(),
+    "B236/S23"
+  );
+}
+
+#[test]
+pub fn test_rule_errors() {
+  for invalid in ["", "B3", "B3/S23/B3", "X3/S23", "B3/S2a"].iter() {
+    assert_eq!(
+      invalid.parse::<Rule>(),
+      Err(RuleError::InvalidFormat(invalid.to_string()))
+    );
+  }
+  assert_eq!("B9/S23".parse::<Rule>(), Err(RuleError::InvalidCount(9)));
+}
+
+#[test]
+pub fn test_universe_rule() {
+  let mut universe = Universe::new(8, 8);
+  assert_eq!(universe.rule(), &Rule::conway());
+  assert_eq!(universe.rule_string(), "B3/S23");
+
+  assert!(universe.set_rule("B36/S23").is_ok());
+  assert_eq!(universe.rule().birth(), &[3, 6]);
+  assert_eq!(
+    universe.set_rule("nope"),
+    Err(RuleError::InvalidFormat("nope".to_string()))
+  );
+  assert_eq!(universe.rule_string(), "B36/S23");
+
+  // Under B1/S a lone cell dies and is surrounded by births.
+  let mut universe = universe_with(7, 7, &[(3, 3)]);
+  universe.set_rule("B1/S").unwrap();
+  universe.tick();
+  assert_eq!(universe.population(), 8);
+  assert_eq!(universe.live_neighbor_count(3, 3), 8);
+}
+
+/// Six cells around a dead center at `(row, col)`, which is born under
+/// HighLife (`B36/S23`) but not under Conway's rules.
+fn hexomino(row: u32, col: u32) -> Vec<(u32, u32)> {
+  vec![
+    (row - 1, col - 1),
+    (row - 1, col),
+    (row - 1, col + 1),
+    (row + 1, col - 1),
+    (row + 1, col),
+    (row + 1, col + 1),
+  ]
+}
+
+/// The live cells of a universe within the columns `cols`, relative to it.
+fn live_in(universe: &Universe, cols: std::ops::Range<u32>) -> Vec<(u32, u32)> {
+  universe
+    .live_cells()
+    .into_iter()
+    .filter(|(_, col)| cols.contains(col))
+    .map(|(row, col)| (row, col - cols.start))
+    .collect()
+}
+
+#[test]
+pub fn test_with_region_rule() {
+  let twins = [hexomino(4, 4), hexomino(4, 14)].concat();
+  let mut universe = universe_with(20, 10, &twins);
+  universe.with_region_rule(0, 10, 10, 10, "B36/S23".parse().unwrap());
+
+  let mut conway = universe_with(10, 10, &hexomino(4, 4));
+
+  universe.tick();
+  conway.tick();
+  let (left, right) = (live_in(&universe, 0..10), live_in(&universe, 10..20));
+  assert_eq!(left, conway.live_cells());
+  assert_ne!(left, right);
+  assert!(right.contains(&(4, 4)));
+  assert!(!left.contains(&(4, 4)));
+
+  // Without the region both halves evolve alike again.
+  universe.clear_region_rules();
+  let mut both = universe_with(20, 10, &twins);
+  both.tick();
+  assert_eq!(live_in(&both, 0..10), live_in(&both, 10..20));
+}
+
+#[test]
+pub fn test_tick_with() {
+  let mut stepped = Universe::soup(16, 16, 42, 0.3);
+  let mut ticked = Universe::soup(16, 16, 42, 0.3);
+  for _ in 0..4 {
+    stepped.tick_with(&Rule::conway());
+    ticked.tick();
+    assert_eq!(stepped.get_cells(), ticked.get_cells());
+  }
+  assert_eq!(stepped.generation(), 4);
+
+  // The stored rule is back in place after the step.
+  stepped.tick_with(&"B36/S23".parse().unwrap());
+  assert_eq!(stepped.rule(), &Rule::conway());
+}
+
+#[test]
+pub fn test_set_rule_from_sets() {
+  let mut universe = Universe::new(8, 8);
+  universe.set_rule_from_sets(&[3, 6], &[2, 3]).unwrap();
+  assert_eq!(universe.rule(), &"B36/S23".parse::<Rule>().unwrap());
+
+  universe.set_rule_from_sets(&[3], &[2, 3]).unwrap();
+  assert_eq!(universe.rule(), &"B3/S23".parse::<Rule>().unwrap());
+
+  // Invalid counts leave the rule unchanged.
+  assert_eq!(
+    universe.set_rule_from_sets(&[3], &[2, 9]),
+    Err(RuleError::InvalidCount(9))
+  );
+  assert_eq!(universe.rule_string(), "B3/S23");
+}
+
+#[test]
+pub fn test_rule_divergence() {
+  let conway = Rule::conway();
+  assert_eq!(
+    rule_divergence(17, 32, 32, &conway, &conway, 20),
+    vec![0; 20]
+  );
+
+  // HighLife only differs from Conway's rules on six neighbors, so the
+  // soups drift apart once such a birth happens.
+  let highlife: Rule = "B36/S23".parse().unwrap();
+  let divergence = rule_divergence(17, 32, 32, &conway, &highlife, 20);
+  assert_eq!(divergence.len(), 20);
+  assert!(divergence.iter().any(|&distance| distance > 0));
+  assert!(rule_divergence(17, 32, 32, &conway, &highlife, 0).is_empty());
+}
+
+#[test]
+pub fn test_hex_neighborhood() {
+  // The six hexagonal neighbors of (2, 2), on an even row, and of (3, 3),
+  // on an odd row, plus the cells of the square grid they leave out.
+  let mut universe = universe_with(
+    6,
+    6,
+    &[
+      (1, 1),
+      (1, 2),
+      (2, 1),
+      (2, 3),
+      (3, 1),
+      (3, 2),
+      (1, 3),
+      (3, 3),
+    ],
+  );
+  assert_eq!(universe.neighborhood(), Neighborhood::Moore);
+  assert_eq!(universe.live_neighbor_count(2, 2), 8);
+  universe.set_neighborhood(Neighborhood::Hex);
+  assert_eq!(universe.live_neighbor_count(2, 2), 6);
+
+  let mut universe = universe_with(
+    6,
+    6,
+    &[
+      (2, 3),
+      (2, 4),
+      (3, 2),
+      (3, 4),
+      (4, 3),
+      (4, 4),
+      (2, 2),
+      (4, 2),
+    ],
+  );
+  universe.set_neighborhood(Neighborhood::Hex);
+  assert_eq!(universe.live_neighbor_count(3, 3), 6);
+
+  // Neighbors wrap around the edges like on the square grid.
+  let mut universe = universe_with(4, 4, &[(3, 0), (3, 3), (0, 3), (0, 1), (1, 3), (1, 0)]);
+  universe.set_neighborhood(Neighborhood::Hex);
+  assert_eq!(universe.live_neighbor_count(0, 0), 6);
+  universe.set_cells(&[(1, 1), (3, 1)]);
+  assert_eq!(universe.live_neighbor_count(0, 0), 6);
+}
+
+#[test]
+pub fn test_reference_digest() {
+  // Pinned so any platform or refactoring which changes the seeding or
+  // the rules makes this fail.
+  assert_eq!(reference_digest(2021, 32, 24, 50), 0x2532_6135_f5f1_3d88);
+
+  // The seed and every generation are part of the digest.
+  assert_ne!(
+    reference_digest(2021, 32, 24, 50),
+    reference_digest(2022, 32, 24, 50)
+  );
+  assert_ne!(
+    reference_digest(2021, 32, 24, 50),
+    reference_digest(2021, 32, 24, 49)
+  );
+}
@@ -0,0 +1,1250 @@
+//! Test suite for the universe's simulation and analysis methods.
+
+use game_of_life::{
+  register_pattern, validate_rle, BufferTooSmall, Cell, Checkpoint, CoordError, DimensionMismatch,
+  ParseError, PatternKind, RleError, Stabilization, Symmetry, TextRenderOptions, Universe,
+  BUILT_IN_PATTERNS,
+};
+
+/// Create a universe of the given size with only the given cells alive.
+pub fn universe_with(width: u32, height: u32, cells: &[(u32, u32)]) -> Universe {
+  let mut universe = Universe::new(width, height);
+  universe.set_width(width);
+  universe.set_height(height);
+  universe.set_cells(cells);
+  universe
+}
+
+#[test]
+pub fn test_weighted_neighbor_sum_uniform_kernel() {
+  let universe = Universe::new(7, 5);
+  let moore = [[1, 1, 1], [1, 0, 1], [1, 1, 1]];
+
+  for row in 0..universe.height() {
+    for col in 0..universe.width() {
+      assert_eq!(
+        universe.weighted_neighbor_sum(row, col, &moore),
+        universe.live_neighbor_count(row, col) as i32
+      );
+    }
+  }
+}
+
+#[test]
+pub fn test_weighted_neighbor_sum_custom_kernel() {
+  // Live cells above, left of and on the center (2, 2).
+  let universe = universe_with(5, 5, &[(1, 2), (2, 1), (2, 2)]);
+  let kernel = [[1, 2, 3], [4, 5, 6], [7, 8, 9]];
+  assert_eq!(universe.weighted_neighbor_sum(2, 2, &kernel), 2 + 4 + 5);
+
+  // (4, 4) wraps around to be the top-left neighbor of (0, 0).
+  let universe = universe_with(5, 5, &[(4, 4)]);
+  assert_eq!(universe.weighted_neighbor_sum(0, 0, &kernel), 1);
+}
+
+#[test]
+pub fn test_tick_reuses_buffers() {
+  let mut universe = universe_with(5, 5, &[(2, 1), (2, 2), (2, 3)]);
+  let vertical = universe_with(5, 5, &[(1, 2), (2, 2), (3, 2)]);
+  let horizontal = universe_with(5, 5, &[(2, 1), (2, 2), (2, 3)]);
+
+  // Swapping the double buffer must never leak a stale generation.
+  for _ in 0..3 {
+    universe.tick_in_place_no_alloc();
+    assert_eq!(universe.get_cells(), vertical.get_cells());
+    universe.tick_in_place_no_alloc();
+    assert_eq!(universe.get_cells(), horizontal.get_cells());
+  }
+}
+
+#[test]
+pub fn test_from_coordinates() {
+  let universe = Universe::from_coordinates(&[(0, 0), (1, 0), (-1, 0)], 5, 5).unwrap();
+  let expected = universe_with(5, 5, &[(1, 2), (2, 2), (3, 2)]);
+  assert_eq!(universe.get_cells(), expected.get_cells());
+
+  assert_eq!(
+    Universe::from_coordinates(&[(0, 0), (0, -3)], 5, 5).err(),
+    Some(ParseError::OutOfBounds { row: 2, col: -1 })
+  );
+}
+
+/// The R-pentomino, a methuselah that keeps growing for a long time.
+pub fn r_pentomino() -> Universe {
+  universe_with(32, 32, &[(15, 16), (15, 17), (16, 15), (16, 16), (17, 16)])
+}
+
+#[test]
+pub fn test_reaches_population() {
+  let mut universe = r_pentomino();
+  let initial = universe.get_cells().to_vec();
+
+  let generation = universe.reaches_population(20, 50);
+  assert_eq!(universe.get_cells(), initial.as_slice());
+  assert_eq!(generation, Some(16));
+
+  // The threshold is crossed exactly at the reported generation.
+  for _ in 1..16 {
+    universe.tick();
+  }
+  assert!(universe.population() < 20);
+  universe.tick();
+  assert!(universe.population() >= 20);
+
+  assert_eq!(r_pentomino().reaches_population(5, 10), Some(0));
+  assert_eq!(r_pentomino().reaches_population(1_000, 10), None);
+}
+
+#[test]
+pub fn test_diff_rle_round_trip() {
+  let mut universe = r_pentomino();
+  let mut target = r_pentomino();
+  for _ in 0..10 {
+    target.tick();
+  }
+
+  let diff = universe.diff_rle(&target).unwrap();
+  assert!(diff.ends_with('!'));
+  universe.apply_diff_rle(&diff).unwrap();
+  assert_eq!(universe.get_cells(), target.get_cells());
+
+  // Identical universes produce an empty diff.
+  assert_eq!(target.diff_rle(&target).unwrap(), "!");
+
+  let blinker = universe_with(5, 5, &[(2, 1), (2, 2), (2, 3)]);
+  let vertical = universe_with(5, 5, &[(1, 2), (2, 2), (3, 2)]);
+  assert_eq!(blinker.diff_rle(&vertical).unwrap(), "$2bo$bobo$2bo!");
+}
+
+#[test]
+pub fn test_diff_rle_errors() {
+  let mut universe = universe_with(5, 5, &[]);
+  assert_eq!(
+    universe.diff_rle(&universe_with(4, 5, &[])),
+    Err(DimensionMismatch {
+      expected: (5, 5),
+      found: (4, 5)
+    })
+  );
+
+  assert_eq!(
+    universe.apply_diff_rle("2bx!"),
+    Err(ParseError::Unexpected {
+      offset: 2,
+      found: 'x'
+    })
+  );
+  assert_eq!(
+    universe.apply_diff_rle("3bo"),
+    Err(ParseError::UnexpectedEnd)
+  );
+  assert_eq!(universe.apply_diff_rle("5o!"), Ok(()));
+  assert_eq!(
+    universe.apply_diff_rle("6o!"),
+    Err(ParseError::OutOfBounds { row: 0, col: 5 })
+  );
+}
+
+#[test]
+pub fn test_nearest_live() {
+  assert_eq!(universe_with(10, 8, &[]).nearest_live(3, 3), None);
+
+  let universe = universe_with(10, 8, &[(5, 6)]);
+  assert_eq!(universe.nearest_live(0, 0), Some((5, 6)));
+  assert_eq!(universe.nearest_live(5, 6), Some((5, 6)));
+
+  // The closest live cell is across the seam.
+  let universe = universe_with(10, 8, &[(0, 1), (4, 5)]);
+  assert_eq!(universe.nearest_live(7, 9), Some((0, 1)));
+  assert_eq!(universe.nearest_live(3, 4), Some((4, 5)));
+
+  // Universes without rows or columns have no cells to find.
+  let mut universe = universe_with(10, 8, &[(4, 5)]);
+  universe.crop(0, 0, 8, 0);
+  assert_eq!(universe.nearest_live(3, 4), None);
+}
+
+#[test]
+pub fn test_sparse_bytes_round_trip() {
+  let mut universe = r_pentomino();
+  for _ in 0..30 {
+    universe.tick();
+  }
+
+  let bytes = universe.to_sparse_bytes();
+  let decoded = Universe::from_sparse_bytes(&bytes).unwrap();
+  assert_eq!(decoded.width(), universe.width());
+  assert_eq!(decoded.height(), universe.height());
+  assert_eq!(decoded.get_cells(), universe.get_cells());
+
+  assert_eq!(
+    Universe::from_sparse_bytes(&bytes[..bytes.len() - 1]).err(),
+    Some(ParseError::UnexpectedEnd)
+  );
+  let mut trailing = bytes.clone();
+  trailing.push(0);
+  assert_eq!(
+    Universe::from_sparse_bytes(&trailing).err(),
+    Some(ParseError::TrailingData {
+      offset: bytes.len()
+    })
+  );
+}
+
+#[test]
+pub fn test_sparse_bytes_reject_huge_dimensions() {
+  // 65536 * 65536 overflows a u32.
+  let mut overflowing = 65536u32.to_le_bytes().to_vec();
+  overflowing.extend_from_slice(&65536u32.to_le_bytes());
+  overflowing.push(0);
+  assert_eq!(
+    Universe::from_sparse_bytes(&overflowing).err(),
+    Some(ParseError::TooLarge {
+      width: 65536,
+      height: 65536
+    })
+  );
+
+  // 60000 * 60000 fits, but is far more than a universe may have.
+  let mut oversized = 60000u32.to_le_bytes().to_vec();
+  oversized.extend_from_slice(&60000u32.to_le_bytes());
+  oversized.push(0);
+  assert_eq!(
+    Universe::from_sparse_bytes(&oversized).err(),
+    Some(ParseError::TooLarge {
+      width: 60000,
+      height: 60000
+    })
+  );
+}
+
+#[test]
+pub fn test_sparse_bytes_reject_overflowing_delta() {
+  // Two live cells, at index 1 and then `u64::MAX` past it.
+  let mut bytes = 4u32.to_le_bytes().to_vec();
+  bytes.extend_from_slice(&4u32.to_le_bytes());
+  bytes.extend_from_slice(&[2, 1]);
+  bytes.extend_from_slice(&[0xff; 9]);
+  bytes.push(0x01);
+  assert_eq!(
+    Universe::from_sparse_bytes(&bytes).err(),
+    Some(ParseError::InvalidVarint { offset: 10 })
+  );
+}
+
+#[test]
+pub fn test_sparse_bytes_beat_dense_bytes() {
+  // A bit-packed grid costs a bit per cell, plus the dimensions.
+  let dense_len = |universe: &Universe| 8 + (universe.width() * universe.height()).div_ceil(8);
+
+  let sparse = r_pentomino();
+  assert!((sparse.to_sparse_bytes().len() as u32) < dense_len(&sparse));
+
+  // Once most cells are alive the dense encoding wins.
+  let crowded = Universe::new(32, 32);
+  assert!((crowded.to_sparse_bytes().len() as u32) > dense_len(&crowded));
+}
+
+#[test]
+pub fn test_tick_with_injection() {
+  let mut universe = universe_with(12, 12, &[]);
+  let mut generations = Vec::new();
+
+  for step in 0..4 {
+    universe.tick_with_injection(|universe, generation| {
+      generations.push(generation);
+
+      // The previously injected lone cell has just died of underpopulation.
+      assert_eq!(universe.population(), 0);
+      universe.set_cells(&[(step * 3, step * 3)]);
+    });
+    assert_eq!(universe.population(), 1);
+  }
+  assert_eq!(generations, vec![1, 2, 3, 4]);
+  assert_eq!(universe.generation(), 4);
+
+  // Still lifes accumulate, so the injected cells keep adding up.
+  let mut universe = universe_with(12, 12, &[]);
+  for step in 0..3 {
+    universe.tick_with_injection(|universe, _| {
+      let (row, col) = (step * 4, step * 4);
+      universe.set_cells(&[
+        (row, col),
+        (row, col + 1),
+        (row + 1, col),
+        (row + 1, col + 1),
+      ]);
+    });
+    assert_eq!(universe.population(), 4 * (step + 1));
+  }
+}
+
+#[test]
+pub fn test_live_hull_area() {
+  let square: Vec<(u32, u32)> = (2..5)
+    .flat_map(|row| (3..6).map(move |col| (row, col)))
+    .collect();
+  assert_eq!(universe_with(10, 10, &square).live_hull_area(), 4.0);
+
+  // A right triangle with legs of 4 cells.
+  let triangle = universe_with(10, 10, &[(1, 1), (1, 5), (5, 1), (2, 2)]);
+  assert_eq!(triangle.live_hull_area(), 8.0);
+
+  // Degenerate patterns have no area.
+  assert_eq!(universe_with(10, 10, &[]).live_hull_area(), 0.0);
+  assert_eq!(universe_with(10, 10, &[(4, 4)]).live_hull_area(), 0.0);
+  assert_eq!(
+    universe_with(10, 10, &[(4, 1), (4, 2), (4, 3), (4, 7)]).live_hull_area(),
+    0.0
+  );
+  assert_eq!(
+    universe_with(10, 10, &[(1, 1), (2, 2), (3, 3)]).live_hull_area(),
+    0.0
+  );
+}
+
+#[test]
+pub fn test_toggle_cell_checked() {
+  let mut universe = universe_with(4, 3, &[(1, 1)]);
+  assert_eq!(universe.toggle_cell_checked(1, 1), Ok(Cell::Dead));
+  assert_eq!(universe.toggle_cell_checked(2, 3), Ok(Cell::Alive));
+  assert_eq!(universe.population(), 1);
+
+  assert_eq!(
+    universe.toggle_cell_checked(3, 0),
+    Err(CoordError {
+      row: 3,
+      col: 0,
+      width: 4,
+      height: 3
+    })
+  );
+  assert_eq!(universe.population(), 1);
+
+  // The unchecked toggle wraps around instead.
+  universe.toggle_cell(3, 0);
+  assert_eq!(universe.get_cells()[0], Cell::Alive);
+}
+
+#[test]
+pub fn test_stabilize() {
+  let mut block = universe_with(8, 8, &[(3, 3), (3, 4), (4, 3), (4, 4)]);
+  assert_eq!(block.stabilize(10), Stabilization::Still(0));
+
+  let mut blinker = universe_with(8, 8, &[(3, 2), (3, 3), (3, 4)]);
+  assert_eq!(
+    blinker.stabilize(10),
+    Stabilization::Oscillator {
+      period: 2,
+      generation: 0
+    }
+  );
+
+  let mut glider = universe_with(12, 12, &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+  assert_eq!(
+    glider.stabilize(10),
+    Stabilization::Spaceship {
+      period: 4,
+      displacement: (1, 1),
+      generation: 0
+    }
+  );
+
+  // A pattern which dies out settles into the empty universe.
+  let mut pair = universe_with(8, 8, &[(3, 3), (3, 4)]);
+  assert_eq!(pair.stabilize(10), Stabilization::Still(1));
+
+  assert_eq!(r_pentomino().stabilize(10), Stabilization::Unresolved);
+}
+
+#[test]
+pub fn test_set_cell() {
+  let mut universe = universe_with(4, 3, &[(0, 0)]);
+  assert_eq!(universe.set_cell(2, 3, Cell::Alive), Ok(()));
+  assert_eq!(universe.set_cell(0, 0, Cell::Dead), Ok(()));
+  assert_eq!(universe.live_cells(), vec![(2, 3)]);
+
+  assert_eq!(
+    universe.set_cell(0, 4, Cell::Alive),
+    Err(CoordError {
+      row: 0,
+      col: 4,
+      width: 4,
+      height: 3
+    })
+  );
+  assert_eq!(universe.live_cells(), vec![(2, 3)]);
+}
+
+#[test]
+pub fn test_live_count_in_radius() {
+  // A plus sign centered on (4, 4), with arms of length 2.
+  let cluster = [
+    (2, 4),
+    (3, 4),
+    (4, 2),
+    (4, 3),
+    (4, 4),
+    (4, 5),
+    (4, 6),
+    (5, 4),
+    (6, 4),
+  ];
+  let mut universe = universe_with(10, 10, &cluster);
+  universe.set_cells(&[(0, 0), (7, 7)]);
+
+  assert_eq!(universe.live_count_in_radius(4, 4, 2, true), 9);
+  assert_eq!(universe.live_count_in_radius(4, 4, 2, false), 8);
+  assert_eq!(universe.live_count_in_radius(4, 4, 3, true), 10);
+  assert_eq!(
+    universe.live_count_in_radius(4, 4, 1, false),
+    universe.live_neighbor_count(4, 4) as u32
+  );
+
+  // The square wraps around the corner to reach (0, 0).
+  assert_eq!(universe.live_count_in_radius(9, 9, 1, false), 1);
+  // A square larger than the universe counts every cell once.
+  assert_eq!(universe.live_count_in_radius(4, 4, 20, true), 11);
+}
+
+#[test]
+pub fn test_population_delta_since() {
+  let mut universe = universe_with(8, 8, &[(1, 1), (2, 2), (3, 3)]);
+  let checkpoint = universe.checkpoint();
+  assert_eq!(universe.population_delta_since(&checkpoint), 0);
+
+  universe.set_cells(&[(5, 5), (6, 6)]);
+  assert_eq!(universe.population_delta_since(&checkpoint), 2);
+
+  universe.toggle_cell(1, 1);
+  universe.toggle_cell(2, 2);
+  universe.toggle_cell(3, 3);
+  universe.toggle_cell(5, 5);
+  assert_eq!(universe.population_delta_since(&checkpoint), -2);
+}
+
+#[test]
+pub fn test_soup_is_deterministic() {
+  let soup = Universe::soup(16, 16, 42, 0.3);
+  assert_eq!(
+    soup.get_cells(),
+    Universe::soup(16, 16, 42, 0.3).get_cells()
+  );
+  assert_ne!(
+    soup.get_cells(),
+    Universe::soup(16, 16, 43, 0.3).get_cells()
+  );
+
+  assert_eq!(Universe::soup(16, 16, 42, 0.0).population(), 0);
+  assert_eq!(Universe::soup(16, 16, 42, 1.0).population(), 256);
+  let population = soup.population();
+  assert!(
+    population > 40 && population < 115,
+    "population {}",
+    population
+  );
+}
+
+#[test]
+pub fn test_new_random() {
+  let first = Universe::new_random(32, 32);
+  std::thread::sleep(std::time::Duration::from_millis(2));
+  let second = Universe::new_random(32, 32);
+  assert_ne!(first.get_cells(), second.get_cells());
+}
+
+#[test]
+pub fn test_transition_counts() {
+  let universe = universe_with(5, 5, &[(2, 1), (2, 2), (2, 3)]);
+  assert_eq!(universe.transition_counts(), None);
+
+  let mut universe = universe.with_transition_counts();
+  assert_eq!(universe.transition_counts(), Some((0, 0)));
+  for _ in 0..7 {
+    universe.tick();
+  }
+  assert_eq!(universe.transition_counts(), Some((14, 14)));
+}
+
+#[test]
+pub fn test_largest_empty_rect() {
+  let universe = universe_with(10, 10, &[(2, 6)]);
+  let (top, left, height, width) = universe.largest_empty_rect().unwrap();
+  assert_eq!((top, left, height, width), (3, 0, 7, 10));
+  assert!(!(top..top + height).contains(&2) || !(left..left + width).contains(&6));
+
+  assert_eq!(
+    universe_with(6, 4, &[]).largest_empty_rect(),
+    Some((0, 0, 4, 6))
+  );
+
+  let full: Vec<(u32, u32)> = (0..3)
+    .flat_map(|row| (0..3).map(move |col| (row, col)))
+    .collect();
+  assert_eq!(universe_with(3, 3, &full).largest_empty_rect(), None);
+
+  // A lone dead cell in a full universe.
+  let mut universe = universe_with(3, 3, &full);
+  universe.toggle_cell(1, 2);
+  assert_eq!(universe.largest_empty_rect(), Some((1, 2, 1, 1)));
+}
+
+#[test]
+pub fn test_mirror_quadrants() {
+  let mut universe = universe_with(8, 6, &[(0, 0), (1, 2), (2, 3), (0, 3)]);
+  universe.set_cells(&[(5, 7), (4, 6)]);
+  universe.mirror_quadrants();
+
+  // Drawing outside of the top-left quadrant is overwritten.
+  let top_left = [(0, 0), (0, 3), (1, 2), (2, 3)];
+  let mut expected = Vec::new();
+  for &(row, col) in &top_left {
+    expected.extend_from_slice(&[
+      (row, col),
+      (row, 7 - col),
+      (5 - row, col),
+      (5 - row, 7 - col),
+    ]);
+  }
+  expected.sort_unstable();
+  expected.dedup();
+  assert_eq!(universe.live_cells(), expected);
+
+  // With odd dimensions the center row and column mirror onto themselves.
+  let mut odd = universe_with(5, 5, &[(2, 0), (0, 2), (2, 2)]);
+  odd.mirror_quadrants();
+  assert_eq!(
+    odd.live_cells(),
+    vec![(0, 2), (2, 0), (2, 2), (2, 4), (4, 2)]
+  );
+}
+
+#[test]
+pub fn test_perimeter() {
+  let block: Vec<(u32, u32)> = (3..7)
+    .flat_map(|row| (2..6).map(move |col| (row, col)))
+    .collect();
+  let universe = universe_with(10, 10, &block);
+  assert_eq!(universe.population(), 16);
+  assert_eq!(universe.perimeter(), 12);
+
+  // Diagonal neighbors don't count, so the corners of a plus are inside.
+  let plus = universe_with(5, 5, &[(1, 2), (2, 1), (2, 2), (2, 3), (3, 2)]);
+  assert_eq!(plus.perimeter(), 4);
+
+  // A band across the whole universe only borders dead cells vertically.
+  let band: Vec<(u32, u32)> = (0..3)
+    .flat_map(|row| (0..6).map(move |col| (row, col)))
+    .collect();
+  assert_eq!(universe_with(6, 6, &band).perimeter(), 12);
+}
+
+#[test]
+pub fn test_would_be_born() {
+  let mut universe = universe_with(5, 5, &[(2, 1), (2, 2), (2, 3)]);
+  assert_eq!(universe.would_be_born(), vec![(1, 2), (3, 2)]);
+
+  // The prediction matches the next generation and doesn't advance it.
+  assert_eq!(universe.generation(), 0);
+  universe.tick();
+  assert!(universe.live_cells().contains(&(1, 2)));
+  assert!(universe.live_cells().contains(&(3, 2)));
+
+  // The actual rule is applied, not just adjacency to live cells.
+  let mut six = universe_with(5, 5, &[(1, 1), (1, 2), (1, 3), (3, 1), (3, 2), (3, 3)]);
+  assert!(!six.would_be_born().contains(&(2, 2)));
+  six.set_rule("B36/S23").unwrap();
+  assert!(six.would_be_born().contains(&(2, 2)));
+}
+
+#[test]
+pub fn test_would_die() {
+  let mut universe = universe_with(5, 5, &[(2, 1), (2, 2), (2, 3)]);
+  assert_eq!(universe.would_die(), vec![(2, 1), (2, 3)]);
+
+  let (born, dying) = (universe.would_be_born(), universe.would_die());
+  universe.tick();
+  let live = universe.live_cells();
+  assert!(born.iter().all(|cell| live.contains(cell)));
+  assert!(dying.iter().all(|cell| !live.contains(cell)));
+
+  let block = universe_with(5, 5, &[(1, 1), (1, 2), (2, 1), (2, 2)]);
+  assert!(block.would_die().is_empty());
+}
+
+#[test]
+pub fn test_peak_population() {
+  let mut universe = r_pentomino();
+  let initial = universe.get_cells().to_vec();
+  let (generation, population) = universe.peak_population(60);
+  assert_eq!(universe.get_cells(), initial.as_slice());
+  assert_eq!(universe.generation(), 0);
+
+  // The methuselah grows well beyond its 5 initial cells.
+  assert!(population > 5 * 5);
+  assert!(generation > 0 && generation <= 60);
+  for _ in 0..60 {
+    universe.tick();
+    assert!(universe.population() <= population);
+    if universe.generation() < generation {
+      assert!(universe.population() < population);
+    }
+    if universe.generation() == generation {
+      assert_eq!(universe.population(), population);
+    }
+  }
+
+  // Shrinking patterns peak right away.
+  let mut pair = universe_with(8, 8, &[(3, 3), (3, 4)]);
+  assert_eq!(pair.peak_population(10), (0, 2));
+}
+
+#[test]
+pub fn test_population_queries_from_a_later_generation() {
+  let mut fresh = r_pentomino();
+  let mut resumed = r_pentomino();
+  resumed.set_generation(500);
+
+  // The peak is reported at its generation, while the other queries count
+  // ticks.
+  let (generation, population) = fresh.peak_population(60);
+  assert_eq!(resumed.peak_population(60), (generation + 500, population));
+
+  let ticks = fresh.reaches_population(20, 50);
+  assert!(ticks.is_some());
+  assert_eq!(resumed.reaches_population(20, 50), ticks);
+
+  let mut diagonal = universe_with(8, 8, &[(2, 2), (3, 3), (4, 4)]);
+  diagonal.set_generation(42);
+  assert_eq!(diagonal.step_count_to_extinction(10), Some(2));
+  assert_eq!(resumed.generation(), 500);
+  assert_eq!(diagonal.generation(), 42);
+}
+
+#[test]
+pub fn test_live_coords_relative_to_bbox() {
+  let glider = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+  for &(row, col) in &[(0, 0), (3, 7), (9, 9)] {
+    let placed: Vec<(u32, u32)> = glider.iter().map(|&(r, c)| (r + row, c + col)).collect();
+    let universe = universe_with(12, 12, &placed);
+    assert_eq!(universe.live_coords_relative_to_bbox(), glider.to_vec());
+  }
+  assert!(universe_with(4, 4, &[])
+    .live_coords_relative_to_bbox()
+    .is_empty());
+}
+
+#[test]
+pub fn test_symmetric_soup() {
+  for &symmetry in Symmetry::ALL.iter() {
+    let soup = Universe::symmetric_soup(16, 16, 7, 0.4, symmetry);
+    assert!(soup.population() > 0);
+    assert!(soup.symmetries().contains(&symmetry), "{:?}", symmetry);
+  }
+
+  // Odd and rectangular universes work for the symmetries they fit.
+  let soup = Universe::symmetric_soup(15, 8, 3, 0.5, Symmetry::D4);
+  assert_eq!(
+    soup.symmetries(),
+    vec![Symmetry::C2, Symmetry::D2, Symmetry::D4]
+  );
+  assert!(!Symmetry::C4.fits(15, 8));
+
+  // A plain soup is very unlikely to be symmetric.
+  assert!(Universe::soup(16, 16, 7, 0.4).symmetries().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "can't have C4 symmetry")]
+pub fn test_symmetric_soup_requires_fit() {
+  Universe::symmetric_soup(16, 8, 7, 0.4, Symmetry::C4);
+}
+
+#[test]
+pub fn test_crop() {
+  let mut universe = r_pentomino();
+  universe.with_region_rule(0, 0, 16, 32, "B36/S23".parse().unwrap());
+  universe.crop(15, 14, 4, 5);
+
+  assert_eq!((universe.width(), universe.height()), (5, 4));
+  assert_eq!(
+    universe.live_cells(),
+    vec![(0, 2), (0, 3), (1, 1), (1, 2), (2, 2)]
+  );
+
+  // The region rule moves with the one row of it left inside the window.
+  let mut expected = universe_with(5, 4, &universe.live_cells());
+  expected.with_region_rule(0, 0, 1, 5, "B36/S23".parse().unwrap());
+  universe.tick();
+  expected.tick();
+  assert_eq!(universe.live_cells(), expected.live_cells());
+
+  // Rectangles are clipped to the universe.
+  universe.crop(2, 3, 10, 10);
+  assert_eq!((universe.width(), universe.height()), (2, 2));
+}
+
+#[test]
+pub fn test_crop_to_nothing() {
+  // Empty windows leave a universe without rows or columns, which the
+  // renderers still draw.
+  for &(height, width) in &[(0, 4), (3, 0), (0, 0)] {
+    let mut universe = r_pentomino();
+    universe.crop(15, 14, height, width);
+    assert_eq!((universe.width(), universe.height()), (width, height));
+    assert_eq!(universe.population(), 0);
+
+    assert_eq!(universe.render(), "");
+    assert_eq!(universe.render_ansi_color(15, 0), "");
+    assert_eq!(universe.to_pbm(), format!("P1\n{} {}\n", width, height));
+    assert!(universe
+      .render_rgba_scaled(0xffffffff, 0x000000ff, 2)
+      .is_empty());
+    assert_eq!(universe.population_by_quadrant(), [0; 4]);
+    assert_eq!(universe.render_diff_text(&universe.clone()), "");
+    assert_eq!(universe.diff_rle(&universe.clone()).unwrap(), "!");
+  }
+}
+
+#[test]
+pub fn test_validate_rle() {
+  let lwss = "#N Lightweight spaceship\n#C Moves right.\nx = 5, y = 4, rule = B3/S23\nbo2bo$o4b$o3b\no$4o!\n";
+  assert_eq!(validate_rle(lwss), Ok((5, 4)));
+  assert_eq!(validate_rle("x=1,y=1\n!"), Ok((1, 1)));
+
+  assert_eq!(
+    validate_rle("#C Nothing here\n\n"),
+    Err(RleError::MissingHeader)
+  );
+  assert_eq!(
+    validate_rle("#C Bad rule\nx = 3, y = 3, rule = B9/S\n3o!"),
+    Err(RleError::InvalidHeader { line: 2 })
+  );
+  assert_eq!(
+    validate_rle("y = 3, x = 3\n3o!"),
+    Err(RleError::InvalidHeader { line: 1 })
+  );
+
+  // Positions are counted across wrapped body lines.
+  assert_eq!(
+    validate_rle("x = 3, y = 2\n3o$\n  bo?!"),
+    Err(RleError::Unexpected {
+      line: 3,
+      col: 5,
+      found: '?'
+    })
+  );
+  assert_eq!(
+    validate_rle("x = 3, y = 2\n3o$4o!"),
+    Err(RleError::OutOfBounds { line: 2, col: 5 })
+  );
+  assert_eq!(
+    validate_rle("x = 3, y = 2\n3o$"),
+    Err(RleError::UnexpectedEnd)
+  );
+}
+
+#[test]
+pub fn test_population_by_quadrant() {
+  let universe = universe_with(8, 8, &[(5, 5), (5, 6), (6, 5), (7, 7)]);
+  assert_eq!(universe.population_by_quadrant(), [0, 0, 0, 4]);
+
+  // The middle row and column of odd sizes go to the bottom and right.
+  let universe = universe_with(5, 3, &[(0, 1), (0, 2), (1, 0), (1, 4)]);
+  assert_eq!(universe.population_by_quadrant(), [1, 1, 1, 1]);
+  assert_eq!(
+    universe_with(1, 1, &[(0, 0)]).population_by_quadrant(),
+    [0, 0, 0, 1]
+  );
+}
+
+#[test]
+pub fn test_stamp_named() {
+  let mut universe = universe_with(8, 8, &[]);
+  universe.stamp_named("glider", 1, 2).unwrap();
+  assert_eq!(
+    universe.live_cells(),
+    vec![(1, 3), (2, 4), (3, 2), (3, 3), (3, 4)]
+  );
+  assert_eq!(universe.stamp_named("nope", 0, 0), Err("unknown pattern"));
+
+  // Stamps wrap around the edges.
+  let mut universe = universe_with(4, 4, &[]);
+  universe.stamp_named("blinker", 3, 2).unwrap();
+  assert_eq!(universe.live_cells(), vec![(3, 0), (3, 2), (3, 3)]);
+
+  // Registered patterns shadow built-ins of the same name.
+  register_pattern("dot", vec![(0, 0)]);
+  register_pattern("dot", vec![(1, 1)]);
+  let mut universe = universe_with(4, 4, &[]);
+  universe.stamp_named("dot", 0, 0).unwrap();
+  assert_eq!(universe.live_cells(), vec![(1, 1)]);
+
+  for (name, coords) in BUILT_IN_PATTERNS {
+    let mut universe = universe_with(8, 8, &[]);
+    universe.stamp_named(name, 0, 0).unwrap();
+    assert_eq!(universe.population() as usize, coords.len(), "{}", name);
+  }
+}
+
+#[test]
+pub fn test_block_entropy() {
+  let dead = universe_with(16, 16, &[]);
+  assert_eq!(dead.block_entropy(2), 0.0);
+  assert_eq!(r_pentomino().block_entropy(0), 0.0);
+
+  // A fair soup gets close to the 4 bits of 2x2 blocks.
+  let soup = Universe::soup(64, 64, 9, 0.5);
+  let entropy = soup.block_entropy(2);
+  assert!(entropy > 3.8 && entropy <= 4.0, "{}", entropy);
+  assert!(soup.block_entropy(1) > 0.95);
+
+  // A lone cell shows up in 4 of the 64 windows.
+  let lone = universe_with(8, 8, &[(3, 3)]);
+  let (p, q) = (4.0 / 64.0f64, 60.0 / 64.0f64);
+  let expected = 4.0 * -(p / 4.0) * (p / 4.0).log2() - q * q.log2();
+  assert!((lone.block_entropy(2) - expected).abs() < 1e-12);
+
+  // Blocks past the universe are clamped to it, however large.
+  assert_eq!(lone.block_entropy(u32::MAX), lone.block_entropy(8));
+  assert_eq!(lone.block_entropy(65536), lone.block_entropy(8));
+}
+
+#[test]
+pub fn test_components() {
+  let universe = universe_with(
+    10,
+    6,
+    &[(0, 0), (1, 1), (0, 5), (0, 6), (1, 5), (1, 6), (4, 8)],
+  );
+  assert_eq!(
+    universe.components(),
+    vec![
+      vec![(0, 0), (1, 1)],
+      vec![(0, 5), (0, 6), (1, 5), (1, 6)],
+      vec![(4, 8)],
+    ]
+  );
+
+  // Components don't connect across the edges.
+  let universe = universe_with(4, 4, &[(0, 0), (3, 3)]);
+  assert_eq!(universe.components().len(), 2);
+  assert!(universe_with(4, 4, &[]).components().is_empty());
+}
+
+#[test]
+pub fn test_find_oscillators() {
+  // A blinker on the left and a block on the right.
+  let mut universe = universe_with(
+    12,
+    7,
+    &[(3, 1), (3, 2), (3, 3), (2, 8), (2, 9), (3, 8), (3, 9)],
+  );
+  let before = universe.get_cells().to_vec();
+
+  assert_eq!(universe.find_oscillators(2), vec![(3, 1, 1, 3)]);
+  assert!(universe.find_oscillators(1).is_empty());
+  assert!(universe.find_oscillators(3).is_empty());
+
+  // The universe is left as it was.
+  assert_eq!(universe.get_cells(), before.as_slice());
+  assert_eq!(universe.generation(), 0);
+}
+
+#[test]
+pub fn test_overlap_count() {
+  let universe = universe_with(8, 8, &[(2, 2), (2, 3), (3, 2), (3, 3)]);
+  let block = universe_with(2, 2, &[(0, 0), (0, 1), (1, 0), (1, 1)]);
+
+  assert_eq!(universe.overlap_count(&block, 3, 3), 1);
+  assert_eq!(universe.overlap_count(&block, 2, 2), 4);
+  assert_eq!(universe.overlap_count(&block, 5, 5), 0);
+
+  // The other universe wraps around the edges.
+  let universe = universe_with(8, 8, &[(0, 0)]);
+  assert_eq!(universe.overlap_count(&block, 7, 7), 1);
+}
+
+#[test]
+pub fn test_ash() {
+  // A blinker, a beehive, a boat and a beacon in its six-cell phase, far
+  // enough apart not to interact.
+  let mut universe = universe_with(
+    20,
+    20,
+    &[
+      (1, 1),
+      (1, 2),
+      (1, 3),
+      (1, 10),
+      (1, 11),
+      (2, 9),
+      (2, 12),
+      (3, 10),
+      (3, 11),
+      (10, 2),
+      (11, 1),
+      (11, 3),
+      (12, 2),
+      (12, 3),
+      (10, 10),
+      (10, 11),
+      (11, 10),
+      (12, 13),
+      (13, 12),
+      (13, 13),
+    ],
+  );
+  assert_eq!(
+    universe.ash(10),
+    Some(vec![
+      PatternKind::Blinker,
+      PatternKind::Beehive,
+      PatternKind::Boat,
+      PatternKind::Beacon,
+    ])
+  );
+  assert!(universe_with(8, 8, &[]).ash(1).unwrap().is_empty());
+
+  // Unnamed objects are reported by their size.
+  let mut snake = universe_with(10, 10, &[(2, 2), (2, 3), (2, 5), (3, 2), (3, 4), (3, 5)]);
+  assert_eq!(
+    snake.ash(4),
+    Some(vec![PatternKind::StillLife { population: 6 }])
+  );
+
+  // A soup burns out into ash, while a lone glider never settles.
+  let mut soup = Universe::soup(24, 24, 11, 0.35);
+  assert!(soup.ash(2000).is_some());
+  let mut glider = universe_with(8, 8, &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+  assert_eq!(glider.ash(100), None);
+}
+
+#[test]
+pub fn test_set_generation() {
+  let mut universe = r_pentomino();
+  universe.set_generation(1103);
+  assert_eq!(universe.generation(), 1103);
+  assert_eq!(universe.population(), 5);
+
+  universe.tick();
+  assert_eq!(universe.generation(), 1104);
+}
+
+#[test]
+pub fn test_reset_generation() {
+  let mut universe = r_pentomino();
+  universe.tick_many(5);
+  let cells = universe.get_cells().to_vec();
+
+  universe.reset_generation();
+  assert_eq!(universe.generation(), 0);
+  assert_eq!(universe.get_cells(), cells.as_slice());
+}
+
+#[test]
+pub fn test_until_stable() {
+  let mut blinker = universe_with(5, 5, &[(2, 1), (2, 2), (2, 3)]);
+  let states: Vec<Checkpoint> = blinker.until_stable().collect();
+  assert_eq!(states.len(), 2);
+  assert_eq!(states[0].generation(), 0);
+  assert_eq!(states[1].generation(), 1);
+  assert_eq!((states[0].width(), states[0].height()), (5, 5));
+  assert_eq!(states[0].live_cells(), vec![(2, 1), (2, 2), (2, 3)]);
+  assert_eq!(states[1].live_cells(), vec![(1, 2), (2, 2), (3, 2)]);
+  assert_eq!(
+    states[1].cells(),
+    universe_with(5, 5, &[(1, 2), (2, 2), (3, 2)]).get_cells()
+  );
+  assert_eq!(blinker.generation(), 2);
+
+  let mut block = universe_with(4, 4, &[(1, 1), (1, 2), (2, 1), (2, 2)]);
+  assert_eq!(block.until_stable().count(), 1);
+
+  // Generations run on without end are bounded by the caller.
+  let mut r = r_pentomino();
+  let populations: Vec<u32> = r
+    .until_stable()
+    .take(4)
+    .map(|state| state.population())
+    .collect();
+  assert_eq!(populations, vec![5, 6, 7, 9]);
+}
+
+#[test]
+pub fn test_cell_eq() {
+  let mut glider = universe_with(6, 6, &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+  glider.tick();
+
+  for &(row, col) in [(1, 0), (1, 2), (2, 1), (2, 2), (3, 1)].iter() {
+    assert!(glider.cell_eq(row, col, Cell::Alive), "({}, {})", row, col);
+  }
+  assert!(glider.cell_eq(0, 1, Cell::Dead));
+  assert!(glider.cell_eq(2, 0, Cell::Dead));
+  assert_eq!(glider.population(), 5);
+}
+
+#[test]
+#[should_panic(expected = "cell (6, 0) is outside of the 6x6 universe")]
+pub fn test_cell_eq_out_of_bounds() {
+  universe_with(6, 6, &[]).cell_eq(6, 0, Cell::Dead);
+}
+
+#[test]
+pub fn test_compact_payload() {
+  let glider = universe_with(8, 8, &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+  let payload = glider.to_compact_payload();
+  assert!(payload.len() < 10, "{} bytes", payload.len());
+  assert!(payload.len() < glider.to_sparse_bytes().len());
+
+  let decoded = Universe::from_compact_payload(&payload).unwrap();
+  assert_eq!((decoded.width(), decoded.height()), (8, 8));
+  assert_eq!(decoded.get_cells(), glider.get_cells());
+
+  let mut universe = r_pentomino();
+  universe.tick_many(30);
+  let decoded = Universe::from_compact_payload(&universe.to_compact_payload()).unwrap();
+  assert_eq!(decoded.get_cells(), universe.get_cells());
+
+  // Gaps running past the last cell and truncated varints are rejected.
+  assert_eq!(
+    Universe::from_compact_payload(&[2, 2, 4]).err(),
+    Some(ParseError::OutOfBounds { row: 2, col: 0 })
+  );
+  assert_eq!(
+    Universe::from_compact_payload(&[8, 0x80]).err(),
+    Some(ParseError::UnexpectedEnd)
+  );
+
+  // Dimensions whose product overflows, or is just too large, are
+  // rejected before anything is allocated.
+  assert_eq!(
+    Universe::from_compact_payload(&[0x80, 0x80, 0x04, 0x80, 0x80, 0x04]).err(),
+    Some(ParseError::TooLarge {
+      width: 65536,
+      height: 65536
+    })
+  );
+  assert_eq!(
+    Universe::from_compact_payload(&[0xe0, 0xd4, 0x03, 0xe0, 0xd4, 0x03]).err(),
+    Some(ParseError::TooLarge {
+      width: 60000,
+      height: 60000
+    })
+  );
+}
+
+#[test]
+pub fn test_neighbor_counts_into() {
+  let blinker = universe_with(5, 5, &[(2, 1), (2, 2), (2, 3)]);
+
+  let mut small = vec![0; 24];
+  assert_eq!(
+    blinker.neighbor_counts_into(&mut small),
+    Err(BufferTooSmall {
+      required: 25,
+      found: 24
+    })
+  );
+
+  let mut buf = vec![9; 26];
+  blinker.neighbor_counts_into(&mut buf).unwrap();
+  assert_eq!(
+    &buf[..25],
+    &[
+      0, 0, 0, 0, 0, //
+      1, 2, 3, 2, 1, //
+      1, 1, 2, 1, 1, //
+      1, 2, 3, 2, 1, //
+      0, 0, 0, 0, 0,
+    ]
+  );
+  assert_eq!(buf[25], 9);
+  assert_eq!(blinker.neighbor_counts(), &buf[..25]);
+}
+
+#[test]
+pub fn test_live_cell_indices() {
+  let mut universe = r_pentomino();
+  universe.tick_many(12);
+
+  let indices = universe.live_cell_indices();
+  assert_eq!(indices.len() as u32, universe.population());
+  assert!(indices.windows(2).all(|pair| pair[0] < pair[1]));
+  for idx in indices {
+    let (row, col) = (idx / universe.width(), idx % universe.width());
+    assert_eq!(universe.get_cells()[idx as usize], Cell::Alive);
+    assert!(universe.live_cells().contains(&(row, col)));
+  }
+}
+
+#[test]
+pub fn test_distance() {
+  let a = Universe::soup(8, 8, 1, 0.5);
+  let mut b = Universe::soup(8, 8, 1, 0.5);
+  assert_eq!(a.distance(&b), Ok(0));
+  b.toggle_cell(0, 0);
+  b.toggle_cell(7, 7);
+  assert_eq!(a.distance(&b), Ok(2));
+  assert_eq!(
+    a.distance(&Universe::soup(4, 8, 1, 0.5)),
+    Err(DimensionMismatch {
+      expected: (8, 8),
+      found: (4, 8)
+    })
+  );
+}
+
+#[test]
+pub fn test_draw_line() {
+  let mut universe = universe_with(6, 6, &[(0, 5)]);
+  universe.draw_line(0, 0, 4, 4, Cell::Alive);
+  assert_eq!(
+    universe.live_cells(),
+    vec![(0, 0), (0, 5), (1, 1), (2, 2), (3, 3), (4, 4)]
+  );
+
+  // Shallow lines step one row every other column, in either direction.
+  let mut universe = universe_with(6, 6, &[]);
+  universe.draw_line(3, 4, 1, 0, Cell::Alive);
+  assert_eq!(
+    universe.live_cells(),
+    vec![(1, 0), (1, 1), (2, 2), (2, 3), (3, 4)]
+  );
+  universe.draw_line(1, 0, 1, 1, Cell::Dead);
+  assert_eq!(universe.population(), 3);
+
+  // Lines running off the universe are clipped.
+  let mut universe = universe_with(4, 4, &[]);
+  universe.draw_line(2, 0, 2, 9, Cell::Alive);
+  assert_eq!(universe.live_cells(), vec![(2, 0), (2, 1), (2, 2), (2, 3)]);
+
+  // Ends far off the universe don't walk the whole line.
+  let mut universe = universe_with(4, 4, &[]);
+  universe.draw_line(0, 0, 0, u32::MAX, Cell::Alive);
+  universe.draw_line(u32::MAX, u32::MAX, 0, 0, Cell::Alive);
+  assert_eq!(
+    universe.live_cells(),
+    vec![(0, 0), (0, 1), (0, 2), (0, 3), (1, 1), (2, 2), (3, 3)]
+  );
+  universe.draw_line(u32::MAX, 0, u32::MAX - 8, 8, Cell::Dead);
+  assert_eq!(universe.population(), 7);
+}
+
+#[test]
+pub fn test_draw_rect_outline() {
+  let mut universe = universe_with(8, 8, &[(3, 3)]);
+  universe.draw_rect_outline(1, 2, 4, 5, Cell::Alive);
+
+  // The border of a 4x5 rectangle has 2 * (4 + 5) - 4 cells.
+  assert_eq!(universe.population(), 14 + 1);
+  for col in 2..7 {
+    assert!(universe.cell_eq(1, col, Cell::Alive));
+    assert!(universe.cell_eq(4, col, Cell::Alive));
+  }
+  for row in 2..4 {
+    assert!(universe.cell_eq(row, 2, Cell::Alive));
+    assert!(universe.cell_eq(row, 6, Cell::Alive));
+    assert!(universe.cell_eq(row, 4, Cell::Dead));
+  }
+
+  // Rectangles running off the universe are clipped.
+  let mut universe = universe_with(4, 4, &[]);
+  universe.draw_rect_outline(2, 2, 10, 10, Cell::Alive);
+  assert_eq!(universe.live_cells(), vec![(2, 2), (2, 3), (3, 2)]);
+  universe.draw_rect_outline(0, 0, 0, 3, Cell::Alive);
+  assert_eq!(universe.population(), 3);
+
+  // Huge rectangles only draw the sides that cross the universe.
+  let mut universe = universe_with(4, 4, &[]);
+  universe.draw_rect_outline(1, 1, u32::MAX, u32::MAX, Cell::Alive);
+  assert_eq!(
+    universe.live_cells(),
+    vec![(1, 1), (1, 2), (1, 3), (2, 1), (3, 1)]
+  );
+  universe.draw_rect_outline(u32::MAX - 1, 0, 2, u32::MAX, Cell::Alive);
+  assert_eq!(universe.population(), 5);
+}
+
+#[test]
+pub fn test_draw_circle() {
+  let mut universe = universe_with(9, 9, &[]);
+  universe.draw_circle(4, 4, 3, Cell::Alive);
+  assert_eq!(
+    universe.render_with(
+      &TextRenderOptions::builder()
+        .alive_glyph('#')
+        .dead_glyph('.')
+        .build()
+        .unwrap()
+    ),
+    concat!(
+      ".........\n",
+      "...###...\n",
+      "..#...#..\n",
+      ".#.....#.\n",
+      ".#.....#.\n",
+      ".#.....#.\n",
+      "..#...#..\n",
+      "...###...\n",
+      ".........\n",
+    )
+  );
+
+  // Circles are clipped, and a radius of 0 is the center alone.
+  let mut universe = universe_with(9, 9, &[]);
+  universe.draw_circle(0, 0, 3, Cell::Alive);
+  assert_eq!(
+    universe.live_cells(),
+    vec![(0, 3), (1, 3), (2, 2), (3, 0), (3, 1)]
+  );
+  let mut universe = universe_with(3, 3, &[]);
+  universe.draw_circle(1, 1, 0, Cell::Alive);
+  assert_eq!(universe.live_cells(), vec![(1, 1)]);
+
+  // Huge circles only trace the arc crossing the universe, which is all
+  // but straight this close to its edge.
+  let mut universe = universe_with(8, 8, &[]);
+  universe.draw_circle(0, 1 << 20, 1 << 20, Cell::Alive);
+  assert_eq!(
+    universe.live_cells(),
+    (0..8).map(|row| (row, 0)).collect::<Vec<_>>()
+  );
+  universe.draw_circle(u32::MAX, u32::MAX, u32::MAX, Cell::Dead);
+  universe.draw_circle(0, 0, u32::MAX, Cell::Dead);
+  assert_eq!(universe.population(), 8);
+}
+
+#[test]
+pub fn test_activity_bounds() {
+  // A glider moving down and to the right by one cell every 4 ticks.
+  let mut glider = universe_with(16, 16, &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+  assert_eq!(glider.activity_bounds(0), (0, 0, 3, 3));
+  assert_eq!(glider.activity_bounds(4), (0, 0, 4, 4));
+  assert_eq!(glider.activity_bounds(8), (0, 0, 5, 5));
+
+  // The glider is restored.
+  assert_eq!(glider.generation(), 0);
+  assert_eq!(
+    glider.live_cells(),
+    vec![(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]
+  );
+  assert_eq!(universe_with(4, 4, &[]).activity_bounds(3), (0, 0, 0, 0));
+}
+
+#[test]
+pub fn test_step_count_to_extinction() {
+  // Only the middle of a diagonal survives a tick, and then it dies too.
+  let mut diagonal = universe_with(8, 8, &[(2, 2), (3, 3), (4, 4)]);
+  assert_eq!(diagonal.step_count_to_extinction(10), Some(2));
+  assert_eq!(diagonal.step_count_to_extinction(1), None);
+  assert_eq!(diagonal.generation(), 0);
+  assert_eq!(diagonal.population(), 3);
+
+  assert_eq!(
+    universe_with(4, 4, &[]).step_count_to_extinction(0),
+    Some(0)
+  );
+  let mut blinker = universe_with(5, 5, &[(2, 1), (2, 2), (2, 3)]);
+  assert_eq!(blinker.step_count_to_extinction(100), None);
+}